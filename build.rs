@@ -0,0 +1,225 @@
+/// Generates the in-process solution registry used by the `advent_of_code` binary with the
+/// `registry` feature. Every scaffolded `src/bin/{year}-{day}.rs` is compiled into `main.rs` as a
+/// module so that `cargo all`, `cargo time` and `cargo verify` can call its parts directly instead
+/// of spawning `cargo run --bin` per day.
+///
+/// Also generates a test for every answer in the examples manifest of a solution, which
+/// `solution!` includes as the `examples` module of its binary.
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut puzzles: Vec<(u16, u8)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let (year, day) = path.file_stem()?.to_str()?.split_once('-')?;
+                    let (year, day): (u16, u8) = (year.parse().ok()?, day.parse().ok()?);
+                    (year >= 2015 && (1..=25).contains(&day)).then_some((year, day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    puzzles.sort_unstable();
+
+    let mut registry = String::new();
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(format!("{year}-{day:02}.rs"));
+        registry.push_str(&format!(
+            "#[cfg(not(any(test, feature = \"dhat-heap\")))]\n\
+             #[allow(dead_code, unused_imports)]\n\
+             #[path = {:?}]\n\
+             mod day_{year}_{day:02};\n",
+            path.display().to_string()
+        ));
+    }
+
+    registry.push_str(
+        "\n#[cfg(not(any(test, feature = \"dhat-heap\")))]\n\
+         pub static SOLUTIONS: &[advent_of_code::template::Solution] = &[\n",
+    );
+    for (year, day) in &puzzles {
+        registry.push_str(&format!("    day_{year}_{day:02}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n");
+
+    registry.push_str(
+        "\n#[cfg(any(test, feature = \"dhat-heap\"))]\n\
+         pub static SOLUTIONS: &[advent_of_code::template::Solution] = &[];\n",
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    write_if_changed(&Path::new(&out_dir).join("solutions.rs"), &registry);
+
+    let data_dir = data_dir(Path::new(&manifest_dir));
+    // Cargo scans watched directories recursively by modification time, so only the examples are
+    // watched instead of inputs, timings and answers. Years without examples yet watch their year
+    // directory until `examples` is created, and new years come with a new binary in `src/bin`.
+    let years: HashSet<u16> = puzzles.iter().map(|(year, _)| *year).collect();
+    let year_dirs = fs::read_dir(&data_dir).into_iter().flatten().flatten();
+    for path in year_dirs
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+    {
+        let examples_dir = path.join("examples");
+        if examples_dir.is_dir() {
+            println!("cargo:rerun-if-changed={}", examples_dir.display());
+        } else if path
+            .file_name()
+            .and_then(|name| name.to_str()?.parse().ok())
+            .is_some_and(|year| years.contains(&year))
+        {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+    let tests_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    // every binary gets a file, as `solution!` includes it regardless of the binary's name.
+    let bins = fs::read_dir(&bin_dir).into_iter().flatten().flatten();
+    for path in bins.map(|entry| entry.path()) {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            let tests = example_tests(name, &data_dir);
+            write_if_changed(&tests_dir.join(format!("{name}.rs")), &tests);
+        }
+    }
+}
+
+/// Only avoids rewriting `path`, binaries are still recompiled whenever the build script reruns.
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return;
+    }
+    fs::write(path, contents).unwrap();
+}
+
+/// The data directory, configured like `paths::data_dir` in the template.
+fn data_dir(root: &Path) -> PathBuf {
+    println!("cargo:rerun-if-env-changed=AOC_DATA_DIR");
+    println!("cargo:rerun-if-changed=aoc.toml");
+
+    let configured = || {
+        let text = fs::read_to_string(root.join("aoc.toml")).ok()?;
+        let config: toml::Table = text.parse().ok()?;
+        config
+            .get("paths")?
+            .get("data")?
+            .as_str()
+            .map(PathBuf::from)
+    };
+
+    let dir = env::var_os("AOC_DATA_DIR")
+        .map(PathBuf::from)
+        .or_else(configured)
+        .unwrap_or_else(|| "data".into());
+    root.join(dir)
+}
+
+/// Tests for the examples manifest of the binary `name`, e.g. `data/2024/examples/16.toml` for
+/// `2024-16`.
+fn example_tests(name: &str, data_dir: &Path) -> String {
+    let Some((year, day)) = name.split_once('-') else {
+        return String::new();
+    };
+
+    let path = data_dir
+        .join(year)
+        .join("examples")
+        .join(format!("{day}.toml"));
+    let Ok(text) = fs::read_to_string(&path) else {
+        return String::new();
+    };
+
+    let mut tests = format!(
+        "// Generated by build.rs from {:?}.\n",
+        path.display().to_string()
+    );
+
+    let examples = match text.parse::<toml::Table>() {
+        Ok(manifest) if manifest.values().all(toml::Value::is_table) => manifest,
+        // let the manifest test report the error when running tests.
+        _ => {
+            println!("cargo:warning=invalid examples manifest {}", path.display());
+            tests.push_str(
+                "\n#[test]\n\
+                 fn manifest() {\n    \
+                     if let Err(e) = ::advent_of_code::template::examples::Manifest::read(super::PUZZLE) {\n        \
+                         panic!(\"{e}\");\n    \
+                     }\n\
+                 }\n",
+            );
+            return tests;
+        }
+    };
+
+    let mut idents = HashSet::new();
+
+    for (name, example) in &examples {
+        let mut ident = test_ident(name);
+        // e.g. `[a-b]` and `[a_b]` both become `a_b`, number the later one instead.
+        if !idents.insert(ident.clone()) {
+            let unique = (2..)
+                .map(|n| format!("{ident}_{n}"))
+                .find(|candidate| !idents.contains(candidate))
+                .unwrap();
+            println!(
+                "cargo:warning=example {name:?} in {} has the same test name as another example, using {unique}",
+                path.display()
+            );
+            idents.insert(unique.clone());
+            ident = unique;
+        }
+
+        for part in [1, 2] {
+            if example.get(format!("part_{part}")).is_some() {
+                tests.push_str(&format!(
+                    "\n#[test]\n\
+                     fn {ident}_part_{part}() {{\n    \
+                         ::advent_of_code::template::examples::check(super::PUZZLE, {:?}, {part}, super::__run_parts);\n\
+                     }}\n",
+                    name
+                ));
+            }
+        }
+    }
+
+    tests
+}
+
+/// Test names need to be snake case identifiers, e.g. `[1]` becomes `example_1` and `["A b"]`
+/// becomes `a_b`.
+fn test_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect();
+
+    if ident.is_empty() {
+        "example".into()
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("example_{ident}")
+    } else {
+        ident
+    }
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2024, 2);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let reports: Vec<Vec<i32>> = input
@@ -18,6 +18,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
+#[allow(clippy::map_all_any_identity)]
 pub fn part_two(input: &str) -> Option<u32> {
     let reports: Vec<Vec<i32>> = input
         .lines()
@@ -53,13 +54,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 }
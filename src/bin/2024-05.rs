@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2024, 5);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (pats, tests) = input.split_once("\n\n").expect("no empty line!");
@@ -88,13 +88,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(123));
     }
 }
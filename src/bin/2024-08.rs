@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2024, 8);
 
 struct AntennaMap {
     height: isize,
@@ -84,13 +84,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(34));
     }
 }
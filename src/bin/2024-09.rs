@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2024, 9, parse = parse);
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 enum Block1 {
@@ -9,51 +9,31 @@ enum Block1 {
 }
 
 #[derive(Clone, Copy, Eq, PartialEq)]
-enum Block2Variant {
+pub enum Block2Variant {
     Empty,
     Data { id: usize },
 }
 
 #[derive(Clone, Copy, Eq, PartialEq)]
-struct BlockData2 {
+pub struct BlockData2 {
     variant: Block2Variant,
     sz: usize,
 }
 
-fn parse1(input: &str) -> Vec<Block1> {
-    let mut disk = Vec::new();
-    for (cur_id, mut chunk) in input
-        .strip_suffix('\n')
-        .unwrap_or(input)
-        .chars()
-        .chunks(2)
-        .into_iter()
-        .enumerate()
-    {
-        let dlen = chunk
-            .next()
-            .unwrap()
-            .to_digit(10)
-            .expect("parse error")
-            .try_into()
-            .expect("u32 to usize error");
-        disk.extend((0..dlen).map(|_i| Block1::Data { id: cur_id }));
-
-        let Some(elen) = chunk.next() else {
-            break;
-        };
-        let elen = elen
-            .to_digit(10)
-            .expect("parse error")
-            .try_into()
-            .expect("u32 to usize error");
-        disk.extend((0..elen).map(|_i| Block1::Empty));
-    }
-    assert!(!disk.is_empty(), "disk is empty!");
-    disk
+/// Expand the disk map into single blocks.
+fn blocks(disk: &[BlockData2]) -> Vec<Block1> {
+    disk.iter()
+        .flat_map(|block_data| {
+            let block = match block_data.variant {
+                Block2Variant::Empty => Block1::Empty,
+                Block2Variant::Data { id } => Block1::Data { id },
+            };
+            std::iter::repeat_n(block, block_data.sz)
+        })
+        .collect()
 }
 
-fn parse2(input: &str) -> Vec<BlockData2> {
+fn parse(input: &str) -> Vec<BlockData2> {
     let mut disk = Vec::new();
     for (cur_id, mut chunk) in input
         .strip_suffix('\n')
@@ -92,8 +72,8 @@ fn parse2(input: &str) -> Vec<BlockData2> {
     disk
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut disk = parse1(input);
+pub fn part_one(disk: &[BlockData2]) -> Option<usize> {
+    let mut disk = blocks(disk);
     let mut r_index = disk.len() - 1;
     let mut w_index = 0;
 
@@ -145,8 +125,8 @@ pub fn part_one(input: &str) -> Option<usize> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut disk = parse2(input);
+pub fn part_two(disk: &[BlockData2]) -> Option<usize> {
+    let mut disk = disk.to_vec();
 
     // Inefficient, but *whatever*.
     let cur_read_id = disk
@@ -219,13 +199,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(2858));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2024, 10);
 
 fn parse(input: &str) -> Vec<Vec<u32>> {
     input
@@ -107,13 +107,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(81));
     }
 }
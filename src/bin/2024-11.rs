@@ -1,8 +1,8 @@
-advent_of_code::solution!(11);
+advent_of_code::solution!(2024, 11);
 
 use memoize::memoize;
 
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::manual_div_ceil)]
 #[memoize]
 // How many stones does the value n create after num_iterations iterations?
 fn count_becomes(n: u64, num_iterations: u32) -> u64 {
@@ -43,7 +43,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(55312));
     }
 }
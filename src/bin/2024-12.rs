@@ -1,4 +1,4 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(2024, 12);
 
 use std::collections::{hash_map::Entry, HashMap};
 
@@ -146,13 +146,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1930));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1206));
     }
 }
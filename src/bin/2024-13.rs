@@ -0,0 +1,142 @@
+advent_of_code::solution!(2024, 13);
+
+use std::{error::Error, fmt::Display, num::ParseIntError};
+
+struct Prize {
+    a: (i64, i64),
+    b: (i64, i64),
+    tgt: (i64, i64),
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    /// The prize starting on this line is missing a line or a field.
+    Malformed(usize),
+    /// A coordinate of the prize starting on this line is not a number.
+    Number(usize, ParseIntError),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Malformed(line) => write!(f, "malformed prize on line {line}"),
+            ParseError::Number(line, _) => write!(f, "invalid number in prize on line {line}"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Malformed(_) => None,
+            ParseError::Number(_, e) => Some(e),
+        }
+    }
+}
+
+// no vectors have either x=0 nor y=0.
+fn parse(input: &str) -> Result<Vec<Prize>, ParseError> {
+    let mut line = 1;
+    input
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| {
+            let prize = parse_prize(p, line);
+            line += p.lines().count() + 1;
+            prize
+        })
+        .collect()
+}
+
+fn parse_prize(p: &str, line: usize) -> Result<Prize, ParseError> {
+    let malformed = || ParseError::Malformed(line);
+    let number = |s: &str| s.parse().map_err(|e| ParseError::Number(line, e));
+
+    let mut l = p.lines();
+    let a = l.next().ok_or_else(malformed)?;
+    let b = l.next().ok_or_else(malformed)?;
+    let t = l.next().ok_or_else(malformed)?;
+
+    let a = a.strip_prefix("Button A: X+").ok_or_else(malformed)?;
+    let b = b.strip_prefix("Button B: X+").ok_or_else(malformed)?;
+    let t = t.strip_prefix("Prize: X=").ok_or_else(malformed)?;
+
+    let (ax, ay) = a.split_once(", Y+").ok_or_else(malformed)?;
+    let (bx, by) = b.split_once(", Y+").ok_or_else(malformed)?;
+    let (tx, ty) = t.split_once(", Y=").ok_or_else(malformed)?;
+
+    Ok(Prize {
+        a: (number(ax)?, number(ay)?),
+        b: (number(bx)?, number(by)?),
+        tgt: (number(tx)?, number(ty)?),
+    })
+}
+
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
+    Ok(parse(input)?
+        .into_iter()
+        .filter_map(|p| {
+            let (ax, ay) = p.a;
+            let (bx, by) = p.b;
+            let (tx, ty) = p.tgt;
+            // thanks to WolframAlpha:
+            // https://www.wolframalpha.com/input?i2d=true&i=a%7B%7BSubscript%5Ba%2Cx%5D%7D%2C%7BSubscript%5Ba%2Cy%5D%7D%7D%2Bb%7B%7BSubscript%5Bb%2Cx%5D%7D%2C%7BSubscript%5Bb%2Cy%5D%7D%7D%3D%7B%7BSubscript%5Bt%2Cx%5D%7D%2C%7BSubscript%5Bt%2Cy%5D%7D%7D+solve+for+%5C%2840%29a%5C%2844%29b%5C%2841%29
+            let a = (by * tx - bx * ty) / (ax * by - ay * bx);
+            let b = (ay * tx - ax * ty) / (ay * bx - ax * by);
+            // check if solution works
+            if a * ax + b * bx == tx && a * ay + b * by == ty {
+                Some(a * 3 + b)
+            } else {
+                None
+            }
+        })
+        .sum())
+}
+
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    Ok(parse(input)?
+        .into_iter()
+        .map(|p| Prize {
+            a: p.a,
+            b: p.b,
+            tgt: (p.tgt.0 + 10000000000000, p.tgt.1 + 10000000000000),
+        })
+        .filter_map(|p| {
+            let (ax, ay) = p.a;
+            let (bx, by) = p.b;
+            let (tx, ty) = p.tgt;
+            // thanks to WolframAlpha:
+            // https://www.wolframalpha.com/input?i2d=true&i=a%7B%7BSubscript%5Ba%2Cx%5D%7D%2C%7BSubscript%5Ba%2Cy%5D%7D%7D%2Bb%7B%7BSubscript%5Bb%2Cx%5D%7D%2C%7BSubscript%5Bb%2Cy%5D%7D%7D%3D%7B%7BSubscript%5Bt%2Cx%5D%7D%2C%7BSubscript%5Bt%2Cy%5D%7D%7D+solve+for+%5C%2840%29a%5C%2844%29b%5C%2841%29
+            let a = (by * tx - bx * ty) / (ax * by - ay * bx);
+            let b = (ay * tx - ax * ty) / (ay * bx - ax * by);
+            // check if solution works
+            if a * ax + b * bx == tx && a * ay + b * by == ty {
+                Some(a * 3 + b)
+            } else {
+                None
+            }
+        })
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.unwrap(), 480);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = part_one("Button A: X+1, Y+2\nButton B: X+3, Y+4\n").unwrap_err();
+        assert!(matches!(error, ParseError::Malformed(1)));
+
+        let input = &advent_of_code::template::read_file("examples", PUZZLE);
+        let error = part_two(&input.replacen("X=12748", "X=127A8", 1)).unwrap_err();
+        assert!(matches!(error, ParseError::Number(5, _)));
+        assert!(error.source().is_some());
+    }
+}
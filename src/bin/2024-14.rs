@@ -1,9 +1,15 @@
-advent_of_code::solution!(14);
+advent_of_code::solution!(2024, 14, params = Params);
 
 use regex::Regex;
 
-const WIDTH: isize = 101;
-const HEIGHT: isize = 103;
+advent_of_code::params! {
+    /// The example uses a smaller room.
+    pub struct Params {
+        width: isize = 101,
+        height: isize = 103,
+        seconds: usize = 100,
+    }
+}
 
 // X,Y here
 fn parse(input: &str, width: isize, height: isize) -> Vec<Vec<(isize, isize)>> {
@@ -58,11 +64,11 @@ fn parse(input: &str, width: isize, height: isize) -> Vec<Vec<(isize, isize)>> {
         .collect()
 }
 
-// Allows to decouple width and height for the test harness.
-fn simulate_pt1(input: &str, width: isize, height: isize, timesteps: usize) -> usize {
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    let (width, height) = (params.width, params.height);
     let routes = parse(input, width, height);
-    let positions: Vec<_> = routes.iter().map(|r| r[timesteps % r.len()]).collect();
-    [
+    let positions: Vec<_> = routes.iter().map(|r| r[params.seconds % r.len()]).collect();
+    let safety_factor = [
         ((0..width / 2), (0..height / 2)),
         ((width / 2 + 1..width), (0..height / 2)),
         ((0..width / 2), (height / 2 + 1..height)),
@@ -75,17 +81,14 @@ fn simulate_pt1(input: &str, width: isize, height: isize, timesteps: usize) -> u
             .filter(|(px, py)| rx.contains(px) && ry.contains(py))
             .count()
     })
-    .product()
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(simulate_pt1(input, WIDTH, HEIGHT, 100))
+    .product();
+    Some(safety_factor)
 }
 
 // We reverse engineer the initial setup by which the problem is generated and in which no tiles
 // overlap.
-fn simulate_pt2(input: &str, width: isize, height: isize) -> Option<usize> {
-    let routes = parse(input, width, height);
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    let routes = parse(input, params.width, params.height);
 
     for time in 1..=10000 {
         let mut positions: Vec<_> = routes.iter().map(|r| r[time % r.len()]).collect();
@@ -101,23 +104,3 @@ fn simulate_pt2(input: &str, width: isize, height: isize) -> Option<usize> {
     }
     None
 }
-
-pub fn part_two(input: &str) -> Option<usize> {
-    simulate_pt2(input, WIDTH, HEIGHT)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = simulate_pt1(
-            &advent_of_code::template::read_file("examples", DAY),
-            11,
-            7,
-            100,
-        );
-        assert_eq!(result, 12);
-    }
-}
@@ -2,7 +2,7 @@ use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use advent_of_code::Grid;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2024, 16, prepare = parse);
 
 const STRAIGHT_COST: usize = 1;
 const TURN_COST: usize = 1000;
@@ -66,8 +66,11 @@ struct BestCostToReach {
     reached_from: Vec<Location>,
 }
 
-fn parse(input: &str) -> ((isize, isize), (isize, isize), Grid<bool>) {
-    let char_grid = Grid::parse(input, |_pos, c| c);
+/// (start_pos, end_pos, walls)
+type Maze = ((isize, isize), (isize, isize), Grid<bool>);
+
+fn parse(input: &str) -> Result<Maze, String> {
+    let char_grid = Grid::try_parse(input, |_pos, c| c).map_err(|e| e.to_string())?;
     let walls = char_grid.map_collect(|_pos, c| *c == '#');
     let start_pos = char_grid
         .iter_tiles()
@@ -75,19 +78,19 @@ fn parse(input: &str) -> ((isize, isize), (isize, isize), Grid<bool>) {
             'S' => Some(pos),
             _ => None,
         })
-        .expect("No start char found");
+        .ok_or("No start char found")?;
     let end_pos = char_grid
         .iter_tiles()
         .find_map(|(pos, c)| match c {
             'E' => Some(pos),
             _ => None,
         })
-        .expect("No end char found");
-    (start_pos, end_pos, walls)
+        .ok_or("No end char found")?;
+    Ok((start_pos, end_pos, walls))
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (start_pos, end_pos, walls) = parse(input);
+pub fn part_one(maze: &Maze) -> Option<usize> {
+    let &(start_pos, end_pos, ref walls) = maze;
 
     let mut heap = BinaryHeap::from([State {
         cost: 0,
@@ -186,8 +189,8 @@ pub fn part_one(input: &str) -> Option<usize> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (start_pos, end_pos, walls) = parse(input);
+pub fn part_two(maze: &Maze) -> Option<usize> {
+    let &(start_pos, end_pos, ref walls) = maze;
 
     let start_loc = Location {
         pos: start_pos,
@@ -369,36 +372,3 @@ pub fn part_two(input: &str) -> Option<usize> {
             .len(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7036));
-    }
-
-    #[test]
-    fn test_part_one_bis() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(11048));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(45));
-    }
-
-    #[test]
-    fn test_part_two_bis() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(64));
-    }
-}
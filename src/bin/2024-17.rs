@@ -1,10 +1,10 @@
-advent_of_code::solution!(17);
+advent_of_code::solution!(2024, 17, prepare = prepare);
 
 use enumn::N;
 use itertools::Itertools;
 use std::rc::Rc;
 #[derive(Clone, Debug)]
-struct Program {
+pub struct Program {
     regs: RegFile,
     ops: Rc<[u8]>,
 }
@@ -29,34 +29,36 @@ enum Opcode {
     Cdv = 7,
 }
 
-fn parse(input: &str) -> Program {
+fn prepare(input: &str) -> Result<Program, String> {
     let mut l = input.lines();
-    let a = l.next().expect("Misssing line A");
-    let b = l.next().expect("Misssing line B");
-    let c = l.next().expect("Misssing line C");
 
-    let a = a.strip_prefix("Register A: ").expect("Missing prefix A");
-    let b = b.strip_prefix("Register B: ").expect("Missing prefix B");
-    let c = c.strip_prefix("Register C: ").expect("Missing prefix C");
+    let mut register = |name: &str| -> Result<u64, String> {
+        l.next()
+            .ok_or(format!("Missing line {name}"))?
+            .strip_prefix(&format!("Register {name}: "))
+            .ok_or(format!("Missing prefix {name}"))?
+            .parse()
+            .map_err(|_| format!("Parse error {name}"))
+    };
 
-    let a = a.parse().expect("Parse error A");
-    let b = b.parse().expect("Parse error B");
-    let c = c.parse().expect("Parse error C");
+    let a = register("A")?;
+    let b = register("B")?;
+    let c = register("C")?;
 
     l.next();
 
     let p = Rc::from(
         l.next()
-            .expect("Missing program")
+            .ok_or("Missing program")?
             .chars()
             .filter_map(|c| c.to_digit(8).map(|i| i as u8))
             .collect::<Vec<_>>(),
     );
 
-    Program {
+    Ok(Program {
         regs: RegFile { a, b, c },
         ops: p,
-    }
+    })
 }
 
 // "Combo" operand value calculation.
@@ -112,18 +114,12 @@ fn exec(Program { regs, ops }: &Program) -> Vec<u8> {
     out
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    Some(
-        exec(&parse(input))
-            .into_iter()
-            .map(|i| i.to_string())
-            .join(","),
-    )
+pub fn part_one(program: &Program) -> Option<String> {
+    Some(exec(program).into_iter().map(|i| i.to_string()).join(","))
 }
 
 // https://todd.ginsberg.com/post/advent-of-code/2024/day17/#d17p2
-pub fn part_two(input: &str) -> Option<u64> {
-    let p = parse(input);
+pub fn part_two(p: &Program) -> Option<u64> {
     p.ops
         .iter()
         .rev()
@@ -149,16 +145,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(117440));
+    fn test_prepare_errors() {
+        assert_eq!(
+            prepare("Register A: 1\nRegister B: x").err(),
+            Some("Parse error B".to_string())
+        );
     }
 }
@@ -1,6 +1,16 @@
 use advent_of_code::{Grid, NEIGHBOURS_ORTHOGONAL_VECTORS};
 use std::collections::{BinaryHeap, HashMap};
-advent_of_code::solution!(18);
+use std::{error::Error, fmt::Display, num::ParseIntError};
+advent_of_code::solution!(2024, 18, params = Params, prepare = prepare);
+
+advent_of_code::params! {
+    /// The example uses a smaller memory space, and fewer bytes fall before part one.
+    pub struct Params {
+        height: isize = 71,
+        width: isize = 71,
+        bytes: usize = 1024,
+    }
+}
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 struct State {
@@ -30,49 +40,77 @@ impl PartialOrd for State {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    part_one_inner(input, 71, 71, 1024)
+/// The memory space, with the number of the byte falling onto each tile, starting at 1, or 0 for
+/// tiles that stay free. Bytes holds the position of every falling byte in order.
+pub struct Memory {
+    appearances: Grid<usize>,
+    bytes: Vec<(isize, isize)>,
 }
 
-pub fn part_two(input: &str) -> Option<&str> {
-    part_two_inner(input, 71, 71)
+#[derive(Debug)]
+pub enum ParseError {
+    /// The byte on this line is not of the form `x,y`.
+    Malformed(usize),
+    /// A coordinate of the byte on this line is not a number.
+    Number(usize, ParseIntError),
+    /// The byte on this line falls outside of the memory space.
+    OutOfRange(usize),
 }
 
-#[inline]
-/// Takes height and width, not max Y and max X.
-fn part_one_inner(input: &str, height: isize, width: isize, restriction: usize) -> Option<usize> {
-    let mut appearances = Grid::new(height, width, |_| 0usize);
-    for (i, l) in input.lines().enumerate() {
-        let (x, y) = l.split_once(",").expect("Parse error");
-        let x = x.parse().expect("Parse error");
-        let y = y.parse().expect("Parse error");
-        *appearances
-            .get_mut((y, x))
-            .expect("Tile index out of range when parsing") = i + 1;
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Malformed(line) => write!(f, "malformed byte on line {line}"),
+            ParseError::Number(line, _) => write!(f, "invalid coordinate on line {line}"),
+            ParseError::OutOfRange(line) => {
+                write!(f, "byte on line {line} is outside of the memory space")
+            }
+        }
     }
-    find_cost(&appearances, restriction)
 }
 
-#[inline]
-/// Takes height and width, not max Y and max X.
-fn part_two_inner(input: &str, height: isize, width: isize) -> Option<&str> {
-    let mut appearances = Grid::new(height, width, |_| 0usize);
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Number(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+fn prepare(input: &str, params: &Params) -> Result<Memory, ParseError> {
+    let mut appearances = Grid::new(params.height, params.width, |_| 0usize);
+    let mut bytes = vec![];
     for (i, l) in input.lines().enumerate() {
-        let (x, y) = l.split_once(",").expect("Parse error");
-        let x = x.parse().expect("Parse error");
-        let y = y.parse().expect("Parse error");
+        let line = i + 1;
+        let number = |s: &str| s.parse().map_err(|e| ParseError::Number(line, e));
+
+        let (x, y) = l.split_once(",").ok_or(ParseError::Malformed(line))?;
+        let (x, y) = (number(x)?, number(y)?);
         *appearances
             .get_mut((y, x))
-            .expect("Tile index out of range when parsing") = i + 1;
+            .ok_or(ParseError::OutOfRange(line))? = line;
+        bytes.push((y, x));
     }
-    (0..(input.lines().count()))
-        .map(|i| (i, find_cost(&appearances, i + 1)))
+    Ok(Memory { appearances, bytes })
+}
+
+pub fn part_one(memory: &Memory, params: &Params) -> Option<usize> {
+    find_cost(&memory.appearances, params.bytes)
+}
+
+pub fn part_two(memory: &Memory) -> Option<String> {
+    (0..memory.bytes.len())
+        .map(|i| (i, find_cost(&memory.appearances, i + 1)))
         // Find first block causing a problem
         .find_map(|(i, cost)| match cost {
             Some(_c) => None,
             None => Some(i),
         })
-        .and_then(|i| input.lines().nth(i))
+        .map(|i| {
+            let (y, x) = memory.bytes[i];
+            format!("{x},{y}")
+        })
 }
 
 /// Takes height and width, not max Y and max X.
@@ -141,26 +179,3 @@ fn find_cost(appearances: &Grid<usize>, restriction: usize) -> Option<usize> {
 
     Some(end_state.cost)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one_inner(
-            &advent_of_code::template::read_file("examples", DAY),
-            7,
-            7,
-            12,
-        );
-        assert_eq!(result, Some(22));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let test_case = advent_of_code::template::read_file("examples", DAY);
-        let result = part_two_inner(&test_case, 7, 7);
-        assert_eq!(result, Some("6,1"));
-    }
-}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(19);
+advent_of_code::solution!(2024, 19);
 
 /// Returns (towels, pats)
 fn parse(input: &str) -> (Vec<&[u8]>, Vec<&[u8]>) {
@@ -49,13 +49,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(16));
     }
 }
@@ -37,6 +37,34 @@ where
     }
 }
 
+/// Error when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GridError {
+    /// The line has a different length than the first line.
+    LineLength {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::LineLength {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line length mismatch parsing grid: line {line} has {found} tiles, expected {expected}."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
 impl<T> Index<(isize, isize)> for Grid<T> {
     type Output = T;
     /// Index by (y,x).
@@ -84,7 +112,16 @@ impl<T> Grid<T> {
     }
     /// Parse 2D map into Grid<T>. transform takes F((y,x),char) -> T. Panics if lines are of unequal
     /// length.
-    pub fn parse<F: FnMut((isize, isize), char) -> T>(input: &str, mut transform: F) -> Grid<T> {
+    pub fn parse<F: FnMut((isize, isize), char) -> T>(input: &str, transform: F) -> Grid<T> {
+        Grid::try_parse(input, transform).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Parse 2D map into Grid<T>. transform takes F((y,x),char) -> T. Fails if lines are of unequal
+    /// length.
+    pub fn try_parse<F: FnMut((isize, isize), char) -> T>(
+        input: &str,
+        mut transform: F,
+    ) -> Result<Grid<T>, GridError> {
         let g: Grid<T> = input
             .lines()
             .enumerate()
@@ -96,13 +133,15 @@ impl<T> Grid<T> {
             })
             .collect::<Vec<_>>()
             .into();
-        assert!(
-            g.contents
-                .iter()
-                .all(|row| row.len() == g.width.try_into().unwrap()),
-            "Line length mismatch parsing grid."
-        );
-        g
+        let width = usize::try_from(g.width).unwrap();
+        match g.contents.iter().position(|row| row.len() != width) {
+            Some(y) => Err(GridError::LineLength {
+                line: y + 1,
+                expected: width,
+                found: g.contents[y].len(),
+            }),
+            None => Ok(g),
+        }
     }
 
     /// Index by (y,x).
@@ -235,3 +274,24 @@ impl<T> Grid<T> {
         }
     }
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+
+    #[test]
+    fn rejects_ragged_grids() {
+        let grid = Grid::try_parse("ab\ncd\n", |_pos, c| c).unwrap();
+        assert_eq!((grid.height, grid.width), (2, 2));
+        assert_eq!(
+            Grid::try_parse("ab\nc\nde", |_pos, c| c),
+            Err(GridError::LineLength {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+}
@@ -1,78 +1,247 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, cross_check, download, inputs, read, scaffold, solve, time, verify,
+};
+use advent_of_code::template::config;
 use args::{parse, AppArguments};
 
+#[cfg(feature = "registry")]
+use advent_of_code::template::registry;
+
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{current_year, Calendar, Day, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::{inputs::Action, scaffold},
+        config, default_year, parse_year,
+        runner::{parse_param, BenchOptions, InputSource},
+        Calendar, Day, Puzzle,
+    };
+    use std::{path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
-            download: bool,
-            overwrite: bool,
+            puzzle: Puzzle,
+            options: scaffold::Options,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            record: bool,
+            force: bool,
+            input: InputSource,
+            params: Vec<(String, String)>,
+        },
+        CrossCheck {
+            puzzle: Puzzle,
+            inputs: Vec<PathBuf>,
         },
         All {
+            year: u16,
             release: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
         },
         Time {
+            year: u16,
             all: bool,
             day: Option<Day>,
             store: bool,
+            bench: BenchOptions,
+            compare: Option<f64>,
+            timeout: Option<Duration>,
+        },
+        Verify {
+            year: u16,
+            all: bool,
+            day: Option<Day>,
+            timeout: Option<Duration>,
+        },
+        Inputs {
+            year: u16,
+            action: Action,
+            day: Option<Day>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// The timeout passed with `--timeout`, or the one set in `aoc.toml`.
+    fn timeout(args: &mut pico_args::Arguments) -> Result<Option<Duration>, pico_args::Error> {
+        Ok(args
+            .opt_value_from_fn("--timeout", parse_timeout)?
+            .or(config::get().run.timeout))
+    }
+
+    /// Parse a timeout given in seconds, e.g. `10` or `0.5`.
+    fn parse_timeout(s: &str) -> Result<Duration, String> {
+        s.parse::<f64>()
+            .ok()
+            .filter(|secs| secs.is_finite() && *secs > 0.0)
+            .map(Duration::from_secs_f64)
+            .ok_or(format!(
+                "invalid timeout `{s}`, expecting a positive number of seconds."
+            ))
+    }
+
+    fn parse_threshold(s: &str) -> Result<f64, String> {
+        s.parse::<f64>()
+            .ok()
+            .filter(|percent| percent.is_finite() && *percent >= 0.0)
+            .ok_or(format!(
+                "invalid threshold `{s}`, expecting a non-negative percentage."
+            ))
+    }
+
+    fn parse_jobs(s: &str) -> Result<usize, String> {
+        s.parse::<usize>()
+            .ok()
+            .filter(|jobs| *jobs > 0)
+            .ok_or(format!(
+                "invalid number of jobs `{s}`, expecting at least 1."
+            ))
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // commands work on the year passed with `--year`, or the year configured in `aoc.toml`.
+        let year = args
+            .opt_value_from_fn("--year", |s| parse_year(s).ok_or("invalid year"))?
+            .or_else(default_year);
+        let year =
+            || year.ok_or("no year specified. Pass `--year <year>` or set `year` in `aoc.toml`.");
+
+        // days are checked against the calendar of the event, which can be shorter than 25 days.
+        let check_day = |day: Day| -> Result<Day, String> {
+            let year = year()?;
+            if Calendar::for_year(year).contains(day) {
+                Ok(day)
+            } else {
+                Err(format!("day {day} is not part of the {year} calendar."))
+            }
+        };
+        let puzzle =
+            |day: Day| -> Result<Puzzle, String> { Ok(Puzzle::new(year()?, check_day(day)?)) };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year: year()?,
                 release: args.contains("--release"),
+                timeout: timeout(&mut args)?,
+                jobs: args.opt_value_from_fn("--jobs", parse_jobs)?.unwrap_or(1),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let bench = BenchOptions::from_args(&mut args)?;
+                let compare = args.contains("--compare");
+                let threshold = args
+                    .opt_value_from_fn("--threshold", parse_threshold)?
+                    .unwrap_or(10.0);
+                let timeout = timeout(&mut args)?;
 
                 AppArguments::Time {
+                    year: year()?,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: args.opt_free_from_str()?.map(check_day).transpose()?,
                     store,
+                    bench,
+                    compare: compare.then_some(threshold),
+                    timeout,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(args.free_from_str()?)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(args.free_from_str()?)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                options: scaffold::Options {
+                    overwrite: args.contains("--overwrite"),
+                    overwrite_input: args.contains("--overwrite-input"),
+                    download: args.contains("--download"),
+                    assume_yes: args.contains("--yes"),
+                    dry_run: args.contains("--dry-run"),
+                    template: args
+                        .opt_value_from_str("--template")?
+                        .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE_NAME.to_string()),
+                },
+                puzzle: puzzle(args.free_from_str()?)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("solve") if args.contains("--inputs") => {
+                let puzzle = puzzle(args.free_from_str()?)?;
+                let mut inputs = vec![];
+                while let Some(input) = args.opt_free_from_str()? {
+                    inputs.push(input);
+                }
+                if inputs.is_empty() {
+                    return Err("`--inputs` expects at least one input file.".into());
+                }
+                AppArguments::CrossCheck { puzzle, inputs }
+            }
+            Some("solve") => {
+                let input = InputSource::from_args(&mut args)?;
+                let submit = args.opt_value_from_str("--submit")?;
+                let params = args.values_from_fn("--param", parse_param)?;
+                if submit.is_some() && (input != InputSource::Input || !params.is_empty()) {
+                    return Err(
+                        "`--submit` can only be used with the puzzle input and its default parameters."
+                            .into(),
+                    );
+                }
+
+                let release = args.contains("--release");
+                let dhat = args.contains("--dhat");
+                let record = args.contains("--record");
+                let force = args.contains("--force");
+
+                // `cargo solve --example 16` takes 16 as the number of the example.
+                let day = match (args.opt_free_from_str()?, &input) {
+                    (Some(day), _) => day,
+                    (None, InputSource::Example(Some(n))) => {
+                        return Err(format!(
+                            "missing day. `--example {n}` selects example {n}, pass the day before it, e.g. `cargo solve {n} --example`."
+                        )
+                        .into());
+                    }
+                    (None, _) => args.free_from_str()?,
+                };
+
+                AppArguments::Solve {
+                    release,
+                    submit,
+                    dhat,
+                    record,
+                    force,
+                    input,
+                    params,
+                    puzzle: puzzle(day)?,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                year: year()?,
+                all: args.contains("--all"),
+                timeout: timeout(&mut args)?,
+                day: args.opt_free_from_str()?.map(check_day).transpose()?,
+            },
+            Some("inputs") => AppArguments::Inputs {
+                year: year()?,
+                action: args.free_from_str()?,
+                day: args.opt_free_from_str()?.map(check_day).transpose()?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -95,45 +264,81 @@ mod args {
     }
 }
 
+/// Solutions compiled into this binary with the `registry` feature, see `build.rs`.
+#[cfg(feature = "registry")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 fn main() {
+    #[cfg(feature = "registry")]
+    registry::register(solutions::SOLUTIONS);
+    // fail early on an invalid `aoc.toml`, instead of in the middle of a command.
+    config::get();
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::CrossCheck { puzzle, inputs } => cross_check::handle(puzzle, &inputs),
+            AppArguments::All {
+                year,
+                release,
+                timeout,
+                jobs,
+            } => all::handle(year, release, timeout, jobs),
+            AppArguments::Time {
+                year,
                 day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
+                all,
+                store,
+                bench,
+                compare,
+                timeout,
+            } => time::handle(year, day, all, store, &bench, compare, timeout),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold { puzzle, options } => scaffold::handle(puzzle, &options),
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                record,
+                force,
+                input,
+                params,
+            } => solve::handle(
+                puzzle, release, dhat, submit, record, force, &input, &params,
+            ),
+            AppArguments::Verify {
+                year,
+                day,
+                all,
+                timeout,
+            } => verify::handle(year, day, all, timeout),
+            AppArguments::Inputs { year, action, day } => inputs::handle(year, day, action),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match Puzzle::today() {
+                    Some(puzzle) => {
+                        let options = scaffold::Options {
+                            download: true,
+                            ..scaffold::Options::default()
+                        };
+                        scaffold::handle(puzzle, &options);
+                        read::handle(puzzle)
                     }
                     None => {
+                        let last = current_year()
+                            .and_then(|year| Calendar::for_year(year).days().last())
+                            .map_or(25, Day::into_inner);
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the {} of december. Please use `scaffold` with a specific day.",
+                            ordinal(last)
                         );
                         process::exit(1)
                     }
@@ -142,3 +347,16 @@ fn main() {
         },
     };
 }
+
+/// Formats `n` as an english ordinal, e.g. `12th`.
+#[cfg(feature = "today")]
+fn ordinal(n: u8) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
@@ -0,0 +1,354 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartResult;
+use crate::template::{paths, Day};
+
+const ANSWERS_FILE_NAME: &str = "answers.json";
+const EXPECTED_ANSWERS_EXTENSION: &str = "answers.json";
+
+/// Represents the known-good answers for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the known-good answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// Outcome of checking the answer of a part against its recorded answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No answer has been recorded for this part yet.
+    Missing,
+}
+
+impl Answers {
+    /// Dehydrate answers to `data/{year}/answers.json`.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
+        let dir = paths::year_dir(year);
+        fs::create_dir_all(&dir)?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(dir.join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from `data/{year}/answers.json`. If not present, returns empty answers.
+    /// Fails if the file can not be read or parsed, instead of treating its answers as missing.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        Answers::read(&paths::year_dir(year).join(ANSWERS_FILE_NAME))
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => Answers::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("{}: {e}", paths::display(path)))
+    }
+
+    /// Path of the file holding the expected answers of an input, e.g. `alice.answers.json` for
+    /// `alice.txt`.
+    pub fn expected_path(input: &Path) -> PathBuf {
+        input.with_extension(EXPECTED_ANSWERS_EXTENSION)
+    }
+
+    /// Read the expected answers of an input for `day`, e.g. `{ "part_1": "42", "part_2": null }`.
+    /// If the input has no expected answers, returns empty answers.
+    pub fn read_expected(input: &Path, day: Day) -> Result<Self, String> {
+        let path = Answers::expected_path(input);
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let json = JsonValue::from_str(&json).or(Err("not valid JSON file."))?;
+        let parts = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(Answers {
+            data: vec![Answer::from_parts(day, parts)?],
+        })
+    }
+
+    /// Get the recorded answer of a part.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the answer of a part, overwriting a previously recorded answer.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Check the answer of a part against the recorded answer.
+    pub fn verify(&self, result: &PartResult) -> Verdict {
+        match self.get(result.day, result.part) {
+            None => Verdict::Missing,
+            Some(expected) if result.answer.as_deref() == Some(expected) => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.into(),
+                actual: result.answer.clone(),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        Answer::from_parts(day, json)
+    }
+}
+
+impl Answer {
+    /// Read the `part_1` and `part_2` keys of a JSON object.
+    fn from_parts(day: Day, json: &HashMap<String, JsonValue>) -> Result<Self, String> {
+        let part = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected answer.{key} to be null or string.")),
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verdict};
+    use crate::{
+        day,
+        template::runner::{Measurement, PartResult},
+    };
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            error: None,
+            measurement: Measurement::default(),
+        }
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("42"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 42 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn reads_expected_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-expected-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("alice.txt");
+
+        assert_eq!(
+            Answers::expected_path(&input),
+            dir.join("alice.answers.json")
+        );
+        assert!(Answers::read_expected(&input, day!(16))
+            .unwrap()
+            .data
+            .is_empty());
+
+        std::fs::write(
+            Answers::expected_path(&input),
+            r#"{ "part_1": "7036", "part_2": null }"#,
+        )
+        .unwrap();
+        let answers = Answers::read_expected(&input, day!(16)).unwrap();
+        assert_eq!(answers.get(day!(16), 1), Some("7036"));
+        assert_eq!(answers.get(day!(16), 2), None);
+
+        std::fs::write(Answers::expected_path(&input), r#"{ "part_1": 7036 }"#).unwrap();
+        assert!(Answers::read_expected(&input, day!(16)).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reads_answer_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("answers.json");
+
+        assert!(Answers::read(&path).unwrap().data.is_empty());
+
+        std::fs::write(&path, r#"{ "data": [{ "day": "01", "part_1": "42" }] }"#).unwrap();
+        assert_eq!(Answers::read(&path).unwrap().get(day!(1), 1), Some("42"));
+
+        std::fs::write(&path, r#"{ "data": [{ "day": "01", "part_1": 42 }] }"#).unwrap();
+        assert!(Answers::read(&path).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(4), 2, "7");
+        answers.set(day!(2), 1, "3");
+        answers.set(day!(4), 2, "8");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(2));
+        assert_eq!(answers.get(day!(4), 2), Some("8"));
+    }
+
+    #[test]
+    fn verifies_results() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+
+        assert_eq!(answers.verify(&result(1, Some("42"))), Verdict::Pass);
+        assert_eq!(
+            answers.verify(&result(1, Some("41"))),
+            Verdict::Fail {
+                expected: "42".into(),
+                actual: Some("41".into())
+            }
+        );
+        assert_eq!(
+            answers.verify(&result(1, None)),
+            Verdict::Fail {
+                expected: "42".into(),
+                actual: None
+            }
+        );
+        assert_eq!(answers.verify(&result(2, Some("1"))), Verdict::Missing);
+    }
+}
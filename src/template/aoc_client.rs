@@ -0,0 +1,366 @@
+/// Built-in client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions, and submits answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::submissions::Outcome;
+use crate::template::{inputs, paths, puzzle, Day, Puzzle};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!(
+    "github.com/nicolandu/aoc2024-rs ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie in `AOC_SESSION` or `~/.adventofcode.session`.
+    MissingSession,
+    /// The server did not accept the session cookie.
+    InvalidSession,
+    /// The puzzle has not been unlocked yet.
+    TooEarly,
+    UnexpectedResponse(String),
+    Http(u16),
+    Transport(String),
+    /// The input could not be stored.
+    Input(inputs::Error),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create `~/.adventofcode.session`."
+            ),
+            AocClientError::InvalidSession => {
+                write!(f, "the session cookie was rejected, it might have expired.")
+            }
+            AocClientError::TooEarly => write!(f, "this puzzle has not been unlocked yet."),
+            AocClientError::UnexpectedResponse(text) => {
+                write!(f, "unexpected response from server: {text}")
+            }
+            AocClientError::Http(status) => write!(f, "server responded with status {status}."),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::Input(e) => write!(f, "{e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<inputs::Error> for AocClientError {
+    fn from(e: inputs::Error) -> Self {
+        AocClientError::Input(e)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Create a client for `year` from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, or `~/.adventofcode.session`.
+    ///  2. the server can be overridden with `AOC_BASE_URL`, e.g. to point at a local stub server.
+    pub fn from_env(year: u16) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    /// Fetch the personal puzzle input for a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/day/{}/input", day.into_inner()))
+    }
+
+    /// Fetch the description of a day as markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&format!("/day/{}", day.into_inner()))?;
+        Ok(puzzle::description_from_html(&html))
+    }
+
+    /// Submit an answer for one part of a day, returning how the server responded to it.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Outcome, AocClientError> {
+        let url = self.url(&format!("/day/{}/answer", day.into_inner()));
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let html = read_response(response)?;
+        let text = puzzle::description_from_html(&html);
+        Outcome::from_response(&text)
+            .ok_or_else(|| AocClientError::UnexpectedResponse(text.trim().to_string()))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let url = self.url(path);
+        read_response(self.request("GET", &url).call())
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}{path}", self.base_url, self.year)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(404, _)) => Err(AocClientError::TooEarly),
+        // only a body asking to log in means the session was rejected, a 500 may be any outage.
+        Err(ureq::Error::Status(status @ (400 | 500), response)) => {
+            let body = response.into_string().unwrap_or_default();
+            if body.contains("log in") {
+                Err(AocClientError::InvalidSession)
+            } else {
+                Err(AocClientError::Http(status))
+            }
+        }
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::Http(status)),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn read(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let description = client.puzzle(puzzle.day)?;
+    write_file(&paths::puzzle_path(puzzle), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+pub fn download(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let puzzle_path = paths::puzzle_path(puzzle);
+
+    let input_path = inputs::write(puzzle, &client.input(puzzle.day)?)?;
+    write_file(&puzzle_path, &client.puzzle(puzzle.day)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        paths::display(&input_path)
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        paths::display(&puzzle_path)
+    );
+    Ok(())
+}
+
+/// Download the description of `puzzle` only, e.g. when its input is already present.
+pub fn download_description(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let puzzle_path = paths::puzzle_path(puzzle);
+
+    write_file(&puzzle_path, &client.puzzle(puzzle.day)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        paths::display(&puzzle_path)
+    );
+    Ok(())
+}
+
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<Outcome, AocClientError> {
+    AocClient::from_env(puzzle.year)?.submit(puzzle.day, part, result)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let path = PathBuf::from(home).join(".adventofcode.session");
+    fs::read_to_string(path).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError};
+    use crate::{day, template::submissions::Outcome};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serve a single canned response on a local port, returns the base URL of the stub server.
+    /// The request line and body are sent back through the join handle.
+    fn stub_server(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    fn answer_page(text: &str) -> &'static str {
+        Box::leak(format!("<main><article><p>{text}</p></article></main>").into_boxed_str())
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, server) = stub_server(200, "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert_eq!(client.input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn fetches_puzzle() {
+        let (base_url, server) = stub_server(
+            200,
+            "<main><article><h2>--- Day 2 ---</h2><p>Hello</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert_eq!(
+            client.puzzle(day!(2)).unwrap(),
+            "## --- Day 2 ---\n\nHello\n"
+        );
+        assert!(server.join().unwrap().starts_with("GET /2024/day/2 "));
+    }
+
+    #[test]
+    fn reports_too_early() {
+        let (base_url, _) = stub_server(404, "Please don't repeatedly request this endpoint.");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert!(matches!(
+            client.input(day!(25)),
+            Err(AocClientError::TooEarly)
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_session() {
+        let (base_url, _) = stub_server(400, "Puzzle inputs differ by user. Please log in.");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::InvalidSession)
+        ));
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (base_url, _) = stub_server(500, "Internal Server Error");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::Http(500))
+        ));
+
+        let (base_url, _) = stub_server(500, "Please log in to get your puzzle input.");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::InvalidSession)
+        ));
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, server) = stub_server(
+            200,
+            answer_page("That's the right answer! You are one gold star closer."),
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert_eq!(client.submit(day!(3), 2, "42").unwrap(), Outcome::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_submission_outcome() {
+        let (base_url, _) = stub_server(
+            200,
+            answer_page("That's not the right answer; your answer is too low."),
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert_eq!(client.submit(day!(3), 1, "41").unwrap(), Outcome::TooLow);
+    }
+
+    #[test]
+    fn reports_unexpected_response() {
+        let (base_url, _) = stub_server(200, answer_page("Something went wrong."));
+        let client = AocClient::new(&base_url, "secret", 2024);
+        assert!(matches!(
+            client.submit(day!(3), 1, "41"),
+            Err(AocClientError::UnexpectedResponse(_))
+        ));
+    }
+}
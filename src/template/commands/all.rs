@@ -1,5 +1,17 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{run_multi, MultiRunOptions};
+use crate::template::Calendar;
+
+pub fn handle(year: u16, is_release: bool, timeout: Option<Duration>, jobs: usize) {
+    run_multi(
+        year,
+        &Calendar::for_year(year).days().collect(),
+        &MultiRunOptions {
+            is_release,
+            timeout,
+            jobs,
+            ..MultiRunOptions::default()
+        },
+    );
 }
@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::run_multi::{run_input, DayStatus};
+use crate::template::runner::PartResult;
+use crate::template::{Calendar, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// Run a solution against several inputs and check each against its expected answers, e.g.
+/// `alice.answers.json` for `alice.txt`. Prints a matrix of inputs and parts.
+/// Exits with a non-zero status if any answer does not match, or a solution failed to run.
+pub fn handle(puzzle: Puzzle, inputs: &[PathBuf]) {
+    let calendar = Calendar::for_year(puzzle.year);
+    let parts: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|part| calendar.has_part(puzzle.day, *part))
+        .collect();
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    for input in inputs {
+        let mut row = vec![input_name(input)];
+
+        match check_input(puzzle, input) {
+            Err(e) => {
+                failed += 1;
+                row.push(format!("✖ {e}"));
+            }
+            Ok((answers, results)) => {
+                for part in &parts {
+                    let Some(result) = results.iter().find(|r| r.part == *part) else {
+                        missing += 1;
+                        row.push("? not implemented".into());
+                        continue;
+                    };
+
+                    let cell = match (&result.error, answers.verify(result)) {
+                        (Some(error), _) => {
+                            failed += 1;
+                            format!("✖ error: {error}")
+                        }
+                        (None, Verdict::Pass) => {
+                            passed += 1;
+                            format!("✔ {}", result.answer.as_deref().unwrap_or_default())
+                        }
+                        (None, Verdict::Fail { expected, actual }) => {
+                            failed += 1;
+                            let actual = actual.unwrap_or_else(|| "nothing".into());
+                            format!("✖ {actual} (expected {expected})")
+                        }
+                        (None, Verdict::Missing) => {
+                            missing += 1;
+                            let answer = result.answer.as_deref().unwrap_or("nothing");
+                            format!("? {answer} (no expected answer)")
+                        }
+                    };
+                    row.push(cell);
+                }
+            }
+        }
+
+        rows.push(row);
+    }
+
+    let header: Vec<String> = ["Input".to_string()]
+        .into_iter()
+        .chain(parts.iter().map(|part| format!("Part {part}")))
+        .collect();
+
+    print_matrix(&header, &rows);
+
+    println!("\n{passed} passed, {failed} failed, {missing} missing.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Run the solution against an input, returning its expected answers along with the results.
+fn check_input(puzzle: Puzzle, input: &Path) -> Result<(Answers, Vec<PartResult>), String> {
+    let answers = Answers::read_expected(input, puzzle.day).map_err(|e| {
+        format!(
+            "could not read \"{}\": {e}",
+            Answers::expected_path(input).display()
+        )
+    })?;
+
+    let (results, status) = run_input(puzzle, input)?;
+
+    match (results, status) {
+        (Some(results), _) => Ok((answers, results)),
+        (None, DayStatus::Failed(reason)) => Err(reason),
+        (None, status) => Err(status.to_string()),
+    }
+}
+
+fn input_name(input: &Path) -> String {
+    input.file_name().map_or_else(
+        || input.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    )
+}
+
+fn print_matrix(header: &[String], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            [header]
+                .into_iter()
+                .chain(rows.iter().map(Vec::as_slice))
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.chars().count())))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(header));
+    println!(
+        "{}",
+        "-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1))
+    );
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+}
@@ -0,0 +1,96 @@
+use std::process;
+use std::str::FromStr;
+
+use crate::template::inputs::{self, Checksums};
+use crate::template::{paths, Calendar, Day, Puzzle};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Encrypt plain text inputs, generating a key if there is none yet.
+    Encrypt,
+    /// Decrypt encrypted inputs to plain text.
+    Decrypt,
+    /// Record the checksums of the current inputs, e.g. after replacing an input on purpose.
+    Checksum,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Action::Encrypt),
+            "decrypt" => Ok(Action::Decrypt),
+            "checksum" => Ok(Action::Checksum),
+            _ => Err(format!(
+                "unknown action `{s}`, expecting `encrypt`, `decrypt` or `checksum`."
+            )),
+        }
+    }
+}
+
+/// Apply `action` to the inputs of `day`, or every day of `year` that has an input.
+/// Checksums of inputs without one are recorded along the way.
+pub fn handle(year: u16, day: Option<Day>, action: Action) {
+    let days: Vec<Day> = day.map_or_else(
+        || Calendar::for_year(year).days().collect(),
+        |day| vec![day],
+    );
+    let mut checksums = Checksums::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+    let mut failed = false;
+
+    for puzzle in days.into_iter().map(|day| Puzzle::new(year, day)) {
+        if !inputs::exists(puzzle) {
+            continue;
+        }
+
+        let input = match inputs::read(puzzle) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {}: ✖ {e}", puzzle.day);
+                failed = true;
+                continue;
+            }
+        };
+
+        let stored = match action {
+            Action::Encrypt if !inputs::plain_path(puzzle).exists() => None,
+            Action::Decrypt if inputs::plain_path(puzzle).exists() => None,
+            Action::Encrypt | Action::Decrypt => {
+                Some(inputs::store(puzzle, &input, action == Action::Encrypt))
+            }
+            Action::Checksum => None,
+        };
+
+        match stored {
+            Some(Ok(path)) => println!("Day {}: wrote \"{}\"", puzzle.day, paths::display(&path)),
+            Some(Err(e)) => {
+                eprintln!("Day {}: ✖ {e}", puzzle.day);
+                failed = true;
+                continue;
+            }
+            None => {}
+        }
+
+        if action == Action::Checksum || !checksums.data.contains_key(&puzzle.day) {
+            checksums.set(puzzle.day, &input);
+            println!(
+                "Day {}: recorded checksum {}…",
+                puzzle.day,
+                &checksums.data[&puzzle.day][..8]
+            );
+        }
+    }
+
+    if let Err(e) = checksums.store_file(year) {
+        eprintln!("Failed to store checksums: {e}");
+        failed = true;
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
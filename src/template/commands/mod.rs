@@ -1,6 +1,9 @@
 pub mod all;
+pub mod cross_check;
 pub mod download;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
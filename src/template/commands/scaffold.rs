@@ -1,25 +1,25 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::examples::{self, Example, Manifest};
+use crate::template::{aoc_client, default_year, inputs, paths, puzzle, Puzzle};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// The `default` template, used if the project has no `templates/default.txt`.
+const DEFAULT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
-}
+pub const DEFAULT_TEMPLATE_NAME: &str = "default";
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +27,582 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create a file that does not exist yet. Fails instead of replacing a file that was created since
+/// scaffolding was planned.
+fn create_new_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Number of lines of each proposed example shown before asking for confirmation.
+const PREVIEW_LINES: usize = 8;
+
+/// Read the template `name` from the `templates` directory, e.g. `templates/grid.txt`.
+fn read_template(name: &str) -> Result<String, String> {
+    let path = paths::templates_dir().join(format!("{name}.txt"));
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_TEMPLATE_NAME => Ok(DEFAULT_TEMPLATE.into()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let mut names: Vec<String> = fs::read_dir(paths::templates_dir())
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    Some(format!("`{}`", name.strip_suffix(".txt")?))
+                })
+                .collect();
+            if !names.iter().any(|n| n == "`default`") {
+                names.push(format!("`{DEFAULT_TEMPLATE_NAME}`"));
+            }
+            names.sort();
+            Err(format!(
+                "unknown template `{name}`, expecting one of {}.",
+                names.join(", ")
+            ))
         }
+        Err(e) => Err(format!(
+            "could not read template \"{}\": {e}",
+            paths::display(&path)
+        )),
+    }
+}
+
+/// Values substituted into templates:
+///  - `%YEAR%`, `%DAY%` (e.g. `01`) and `%DAY_NUMBER%` (e.g. `1`).
+///  - `%TITLE%`, e.g. `Day 1: Historian Hysteria`, or `Day 1` if the description was not downloaded.
+///  - `%PART_ONE_ANSWER%` and `%PART_TWO_ANSWER%`, the answers of the examples or `unknown`.
+///  - `%PART_ONE_TYPE%` and `%PART_TWO_TYPE%`, the answer types suggested by the examples, e.g.
+///    `u64` or `String`. Defaults to `u32`.
+///
+/// If the examples manifest has answers, its generated tests replace the placeholder tests.
+fn render_module(template: &str, puzzle: Puzzle, title: &str, manifest: &Manifest) -> String {
+    let answer = |part| {
+        manifest
+            .examples
+            .iter()
+            .find_map(|example| example.answer(part))
     };
+    let (part_one, part_two) = (answer(1), answer(2));
+    let module = template
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%TITLE%", title)
+        .replace("%PART_ONE_ANSWER%", part_one.unwrap_or("unknown"))
+        .replace("%PART_TWO_ANSWER%", part_two.unwrap_or("unknown"))
+        .replace("%PART_ONE_TYPE%", answer_type(part_one))
+        .replace("%PART_TWO_TYPE%", answer_type(part_two));
+    render_tests(&module, !manifest.has_answers())
+}
+
+/// Marker lines around the placeholder tests of a template.
+const TESTS_MARKER: &str = "// {{TESTS}}";
+const TESTS_END_MARKER: &str = "// {{/TESTS}}";
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+/// Remove the marker lines around the placeholder tests of a module, and the tests themselves
+/// unless `keep_tests` is set.
+fn render_tests(module: &str, keep_tests: bool) -> String {
+    let mut in_tests = false;
+    let mut lines = vec![];
+    for line in module.lines() {
+        match line.trim() {
+            TESTS_MARKER => in_tests = true,
+            TESTS_END_MARKER => in_tests = false,
+            _ if in_tests && !keep_tests => {}
+            _ => lines.push(line),
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    }
+    format!("{}\n", lines.join("\n").trim_end())
+}
+
+fn answer_type(answer: Option<&str>) -> &'static str {
+    match answer {
+        None => "u32",
+        Some(answer) if answer.parse::<u64>().is_ok() => "u64",
+        Some(answer) if answer.parse::<i64>().is_ok() => "i64",
+        Some(_) => "String",
+    }
+}
+
+/// Options of the `scaffold` command.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Replace an existing module file.
+    pub overwrite: bool,
+    /// Replace an existing, non-empty input. Only asked for explicitly, as inputs are not in git.
+    pub overwrite_input: bool,
+    /// Download the input and description.
+    pub download: bool,
+    /// Write the examples found in the description without confirmation.
+    pub assume_yes: bool,
+    /// Only report the plan, without changing any file.
+    pub dry_run: bool,
+    /// Name of the template, e.g. `grid` for `templates/grid.txt`.
+    pub template: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            overwrite: false,
+            overwrite_input: false,
+            download: false,
+            assume_yes: false,
+            dry_run: false,
+            template: DEFAULT_TEMPLATE_NAME.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    Keep,
+    Overwrite,
+}
+
+/// What scaffolding does to one file, along with the reason for keeping a file.
+struct Step {
+    name: &'static str,
+    path: PathBuf,
+    action: Action,
+    reason: Option<&'static str>,
+}
+
+impl Step {
+    fn new(name: &'static str, path: PathBuf, action: Action) -> Self {
+        Step {
+            name,
+            path,
+            action,
+            reason: None,
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    fn keep(name: &'static str, path: PathBuf, reason: &'static str) -> Self {
+        Step {
+            reason: Some(reason),
+            ..Step::new(name, path, Action::Keep)
         }
-        Err(e) => {
+    }
+
+    fn report(&self, dry_run: bool) {
+        let verb = match (self.action, dry_run) {
+            (Action::Create, false) => "Created",
+            (Action::Create, true) => "Would create",
+            (Action::Keep, false) => "Kept",
+            (Action::Keep, true) => "Would keep",
+            (Action::Overwrite, false) => "Overwrote",
+            (Action::Overwrite, true) => "Would overwrite",
+        };
+        let reason = self.reason.map(|r| format!(" ({r})")).unwrap_or_default();
+        println!(
+            "{verb} {} \"{}\"{reason}",
+            self.name,
+            paths::display(&self.path)
+        );
+    }
+}
+
+/// The files scaffolding `puzzle` creates, keeps or overwrites. Existing examples, manifests and
+/// non-empty inputs are kept, unless `overwrite_input` is set for the input.
+struct Plan {
+    module: Step,
+    input: Step,
+    example: Step,
+    manifest: Step,
+}
+
+impl Plan {
+    fn new(puzzle: Puzzle, options: &Options) -> Self {
+        let module_path = paths::bin_path(puzzle);
+        let module = match (module_path.exists(), options.overwrite) {
+            (false, _) => Step::new("module file", module_path, Action::Create),
+            (true, true) => Step::new("module file", module_path, Action::Overwrite),
+            (true, false) => Step::keep(
+                "module file",
+                module_path,
+                "exists, pass `--overwrite` to replace it",
+            ),
+        };
+
+        // written inputs are downloaded, or empty.
+        let name = if options.download {
+            "input file"
+        } else {
+            "empty input file"
+        };
+        let plain = inputs::plain_path(puzzle);
+        let encrypted = inputs::encrypted_path(puzzle);
+        // only the size of an existing input matters, its contents may not be valid UTF-8.
+        let input = if let Ok(metadata) = fs::metadata(&plain) {
+            match (
+                metadata.len() == 0,
+                options.overwrite_input,
+                options.download,
+            ) {
+                (true, _, true) | (false, true, _) => Step::new(name, plain, Action::Overwrite),
+                (true, _, false) => Step::keep("input file", plain, "empty"),
+                (false, false, _) => Step::keep(
+                    "input file",
+                    plain,
+                    "not empty, pass `--overwrite-input` to replace it",
+                ),
+            }
+        } else if encrypted.exists() {
+            // an empty input file would take precedence over an encrypted input.
+            if options.overwrite_input {
+                Step::new(name, encrypted, Action::Overwrite)
+            } else {
+                Step::keep(
+                    "encrypted input file",
+                    encrypted,
+                    "pass `--overwrite-input` to replace it",
+                )
+            }
+        } else {
+            Step::new(name, plain, Action::Create)
+        };
+
+        let example_path = paths::data_path("examples", puzzle);
+        let example = match fs::metadata(&example_path) {
+            Ok(metadata) if metadata.len() == 0 => {
+                Step::keep("example file", example_path, "empty")
+            }
+            Ok(_) => Step::keep("example file", example_path, "not empty"),
+            Err(_) => Step::new("empty example file", example_path, Action::Create),
+        };
+
+        // examples are only proposed for puzzles without a manifest.
+        let manifest_path = Manifest::path(puzzle);
+        let manifest = if manifest_path.exists() {
+            Step::keep("examples manifest", manifest_path, "exists")
+        } else {
+            Step::new("examples manifest", manifest_path, Action::Create)
+        };
+
+        Plan {
+            module,
+            input,
+            example,
+            manifest,
+        }
+    }
+}
+
+/// Scaffold the module, input and example of `puzzle`, optionally downloading its input and
+/// description. Examples found in the description are written once confirmed.
+///
+/// Scaffolding is planned upfront, see [`Plan`]. With `dry_run`, only the plan is reported.
+pub fn handle(puzzle: Puzzle, options: &Options) {
+    let Puzzle { year, day } = puzzle;
+
+    let template = read_template(&options.template).unwrap_or_else(|e| {
+        eprintln!("Failed to scaffold: {e}");
+        process::exit(1);
+    });
+
+    let plan = Plan::new(puzzle, options);
+
+    if options.dry_run {
+        let examples = plan_examples(puzzle, &plan.manifest).unwrap_or_default();
+        plan.input.report(true);
+        // a proposed example replaces the empty example file.
+        if !examples
+            .iter()
+            .any(|(_, _, step)| step.path == plan.example.path)
+        {
+            plan.example.report(true);
+        }
+        if options.download {
+            let what = if plan.input.action == Action::Keep {
+                "description"
+            } else {
+                "input and description"
+            };
+            println!("Would download the {what}");
+        }
+        if examples.is_empty() && plan.manifest.action != Action::Keep {
+            if options.download {
+                println!("Would propose the examples of the description");
+            }
+        } else {
+            for (_, _, step) in &examples {
+                step.report(true);
+            }
+            plan.manifest.report(true);
+        }
+        plan.module.report(true);
+        return;
+    }
+
+    let Plan {
+        module,
+        input,
+        example,
+        manifest,
+    } = plan;
+
+    if input.action != Action::Keep && !options.download {
+        if let Err(e) = inputs::store(puzzle, "", false) {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
+    input.report(false);
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
+    if example.action != Action::Keep {
+        if let Err(e) = create_new_file(&example.path) {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
+    example.report(false);
+
+    if options.download {
+        let downloaded = if input.action == Action::Keep {
+            aoc_client::download_description(puzzle)
+        } else {
+            aoc_client::download(puzzle)
+        };
+        if let Err(e) = downloaded {
+            eprintln!("failed to download puzzle: {e}");
+            process::exit(1);
+        }
+    }
+
+    propose_examples(puzzle, &manifest, options.assume_yes);
+
+    if module.action == Action::Keep {
+        module.report(false);
+    } else {
+        let description = fs::read_to_string(paths::puzzle_path(puzzle)).unwrap_or_default();
+        let title = puzzle::title(&description)
+            .map_or_else(|| format!("Day {}", day.into_inner()), str::to_string);
+        let manifest = Manifest::read(puzzle).unwrap_or_default();
+        let contents = render_module(&template, puzzle, &title, &manifest);
+        // without markers, placeholder tests would assert `None` against the answers of the examples.
+        if manifest.has_answers() && !template.contains(TESTS_MARKER) {
+            eprintln!(
+                "Template \"{}\" has no `{TESTS_MARKER}` marker, replace its placeholder tests with \
+                 the tests of the examples manifest.",
+                options.template
+            );
+        }
+
+        let file = match module.action {
+            Action::Create => create_new_file(&module.path),
+            _ => create_file(&module.path),
+        };
+        if let Err(e) = file.and_then(|mut file| file.write_all(contents.as_bytes())) {
+            eprintln!("Failed to create module file: {e}");
+            process::exit(1);
+        }
+        module.report(false);
+    }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if default_year() == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+}
+
+/// The examples and answers found in the downloaded description of `puzzle`, along with the step
+/// writing each of them. Existing, non-empty example files are kept.
+///
+/// Returns `None` if the description was not downloaded, or the puzzle already has examples.
+fn plan_examples(puzzle: Puzzle, manifest: &Step) -> Option<Vec<(Example, String, Step)>> {
+    if manifest.action == Action::Keep {
+        return None;
+    }
+
+    let description = fs::read_to_string(paths::puzzle_path(puzzle)).ok()?;
+
+    let example_path = paths::data_path("examples", puzzle);
+    let has_example = match fs::read(&example_path) {
+        Ok(contents) => !contents.trim_ascii().is_empty(),
+        Err(e) => e.kind() != io::ErrorKind::NotFound,
+    };
+    if has_example {
+        return None;
+    }
+
+    let examples_dir = examples::examples_dir(puzzle);
+    let examples = puzzle::propose_examples(&description)
+        .into_iter()
+        .enumerate()
+        .map(|(i, proposal)| {
+            let (name, file) = match i {
+                0 => ("example".to_string(), format!("{}.txt", puzzle.day)),
+                i => (
+                    format!("example_{}", i + 1),
+                    format!("{}-{}.txt", puzzle.day, i + 1),
+                ),
+            };
+            let path = examples_dir.join(&file);
+            let step = match fs::metadata(&path) {
+                Ok(metadata) if metadata.len() == 0 => {
+                    Step::new("example file", path, Action::Overwrite)
+                }
+                Ok(_) => Step::keep(
+                    "example file",
+                    path,
+                    "not empty, its proposed answers are not recorded",
+                ),
+                Err(_) => Step::new("example file", path, Action::Create),
+            };
+            let example = Example {
+                name,
+                file: file.into(),
+                part_1: proposal.part_1,
+                part_2: proposal.part_2,
+                params: vec![],
+            };
+            (example, proposal.input, step)
+        })
+        .collect();
+
+    Some(examples)
+}
+
+/// Propose the examples and answers found in the downloaded description of `puzzle`, and write
+/// them to the example files and manifest once confirmed, see [`plan_examples`].
+fn propose_examples(puzzle: Puzzle, manifest: &Step, assume_yes: bool) {
+    let Some(examples) = plan_examples(puzzle, manifest) else {
+        if manifest.action == Action::Keep {
+            manifest.report(false);
+        }
+        return;
+    };
+
+    let description_path = paths::puzzle_path(puzzle);
+    if examples.is_empty() {
+        println!(
+            "Found no examples with answers in \"{}\"",
+            paths::display(&description_path)
+        );
+        return;
+    }
+
+    println!(
+        "Found examples in \"{}\":",
+        paths::display(&description_path)
+    );
+    for (example, input, step) in &examples {
+        println!("  \"{}\"", paths::display(&step.path));
+        for line in input.lines().take(PREVIEW_LINES) {
+            println!("    │ {line}");
+        }
+        if input.lines().count() > PREVIEW_LINES {
+            println!("    │ …");
+        }
+        for part in [1, 2] {
+            if let Some(answer) = example.answer(part) {
+                println!("    part {part}: {answer}");
+            }
+        }
+    }
+
+    if !assume_yes && !confirm("Write these examples and expected answers?") {
+        return;
+    }
+
+    let mut written = vec![];
+    for (example, input, step) in examples {
+        let file = match step.action {
+            Action::Keep => {
+                step.report(false);
+                continue;
+            }
+            Action::Create => create_new_file(&step.path),
+            Action::Overwrite => create_file(&step.path),
+        };
+        if let Err(e) = file.and_then(|mut file| file.write_all(input.as_bytes())) {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+        step.report(false);
+        written.push(example);
+    }
+
+    let contents = Manifest { examples: written }.to_toml(puzzle);
+    if let Err(e) =
+        create_new_file(&manifest.path).and_then(|mut file| file.write_all(contents.as_bytes()))
+    {
+        eprintln!("Failed to write examples manifest: {e}");
+        process::exit(1);
+    }
+    manifest.report(false);
+}
+
+/// Ask a yes/no question, defaulting to no. Outside of a terminal, the answer is always no.
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        println!("Skipped examples, pass `--yes` to write them without confirmation.");
+        return false;
+    }
+
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_module, render_tests};
+    use crate::template::examples::{Example, Manifest};
+    use crate::{day, template::Puzzle};
+
+    const MODULE: &str =
+        "pub fn part_one() {}\n\n// {{TESTS}}\n#[cfg(test)]\nmod tests {}\n// {{/TESTS}}\n";
+
+    #[test]
+    fn renders_placeholder_tests() {
+        assert_eq!(
+            render_tests(MODULE, true),
+            "pub fn part_one() {}\n\n#[cfg(test)]\nmod tests {}\n"
+        );
+        assert_eq!(render_tests(MODULE, false), "pub fn part_one() {}\n");
+    }
+
+    #[test]
+    fn replaces_placeholder_tests_with_example_tests() {
+        let templates = [
+            include_str!("../../../templates/default.txt"),
+            include_str!("../../../templates/grid.txt"),
+            include_str!("../../../templates/parse-once.txt"),
+            include_str!("../../../templates/graph.txt"),
+        ];
+        let puzzle = Puzzle::new(2024, day!(1));
+        let manifest = Manifest {
+            examples: vec![Example {
+                name: "example".into(),
+                file: "01.txt".into(),
+                part_1: Some("11".into()),
+                part_2: None,
+                params: vec![],
+            }],
+        };
+
+        for template in templates {
+            let module = render_module(template, puzzle, "Day 1", &manifest);
+            assert!(!module.contains("#[cfg(test)]"), "{module}");
+            assert!(module.contains("Option<u64>"));
+            assert!(!module.contains("{{"));
+
+            let module = render_module(template, puzzle, "Day 1", &Manifest::default());
+            assert!(module.contains("#[cfg(test)]"));
+        }
+    }
 }
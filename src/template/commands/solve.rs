@@ -1,9 +1,20 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::runner::InputSource;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    record: bool,
+    force: bool,
+    input: &InputSource,
+    params: &[(String, String)],
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -23,6 +34,21 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if record {
+        cmd_args.push("--record".to_string());
+    }
+
+    if force {
+        cmd_args.push("--force".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    for (key, value) in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(format!("{key}={value}"));
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
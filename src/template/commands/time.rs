@@ -1,34 +1,75 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::template::run_multi::{run_multi, MultiRunOptions};
+use crate::template::runner::{BenchOptions, PARSE_PART};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, Calendar, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Bench days and optionally store the results. If `compare` holds a regression threshold in
+/// percent, results are compared against the stored timings and the process exits with a non-zero
+/// status if any part got slower than the threshold allows, or there is no baseline to compare
+/// against.
+pub fn handle(
+    year: u16,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: &BenchOptions,
+    compare: Option<f64>,
+    timeout: Option<Duration>,
+) {
+    // fail instead of treating unreadable timings as empty, which would pass every comparison and
+    // overwrite them on `--store`.
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+    let calendar = Calendar::for_year(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                calendar.days().collect()
+            } else if compare.is_some() {
+                // when comparing, only run days that have a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                calendar
+                    .days()
+                    .filter(|day| !stored_timings.is_day_complete(*day, &calendar))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if compare.is_some() && !has_baseline(&stored_timings, &days_to_run) {
+        eprintln!("Error: no stored timings with statistics to compare against, run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let options = MultiRunOptions {
+        is_release: true,
+        is_timed: true,
+        bench: *bench,
+        timeout,
+        ..MultiRunOptions::default()
+    };
+    let timings = run_multi(year, &days_to_run, &options).unwrap();
+
+    let has_regressions = compare.is_some_and(|threshold| {
+        print_comparison(&timings, &stored_timings, &days_to_run, threshold)
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +78,66 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressions {
+        process::exit(1);
+    }
+}
+
+/// Whether any of `days` has stored statistics to compare against.
+fn has_baseline(stored_timings: &Timings, days: &HashSet<Day>) -> bool {
+    stored_timings.data.iter().any(|t| {
+        days.contains(&t.day)
+            && (t.parse_stats.is_some() || t.part_1_stats.is_some() || t.part_2_stats.is_some())
+    })
+}
+
+/// Print per-part deltas against the stored timings, returns whether any part regressed.
+/// Parts with a baseline that are missing from the run, e.g. because their day failed or timed out,
+/// count as regressions.
+fn print_comparison(
+    timings: &Timings,
+    stored_timings: &Timings,
+    days: &HashSet<Day>,
+    threshold: f64,
+) -> bool {
+    let comparisons = timings.compare(stored_timings, days);
+
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET} (median, threshold: +{threshold:.1}%)");
+    println!("----------");
+
+    for comparison in &comparisons {
+        let label = match comparison.part {
+            PARSE_PART => format!("Day {} Parse", comparison.day),
+            part => format!("Day {} Part {part}", comparison.day),
+        };
+        let marker = if comparison.is_regression(threshold) {
+            " ✖ regression"
+        } else {
+            ""
+        };
+        match (comparison.old, comparison.new, comparison.change_percent()) {
+            (Some(old), Some(new), Some(change)) => {
+                println!("{label}: {old:.1?} → {new:.1?} ({change:+.1}%){marker}");
+            }
+            (Some(old), Some(new), None) => println!("{label}: {old:.1?} → {new:.1?}"),
+            (Some(old), None, _) => println!("{label}: {old:.1?} → missing{marker}"),
+            (None, Some(new), _) => println!("{label}: {new:.1?} (no baseline)"),
+            (None, None, _) => {}
+        }
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+
+    if regressions > 0 {
+        println!("\n{regressions} part(s) regressed by more than {threshold:.1}% or are missing.");
+    } else {
+        println!("\nNo regressions.");
+    }
+
+    regressions > 0
 }
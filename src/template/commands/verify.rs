@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::inputs::{self, Integrity};
+use crate::template::run_multi::{run_days, DayStatus, MultiRunOptions};
+use crate::template::{Calendar, Day, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// Run solutions against their real inputs and check the answers against `data/{year}/answers.json`.
+/// Exits with a non-zero status if any answer does not match its recorded answer or is missing, a
+/// solution failed to run, or an input can not be checked against or does not match its checksum
+/// in `data/{year}/checksums.json`.
+pub fn handle(year: u16, day: Option<Day>, run_all: bool, timeout: Option<Duration>) {
+    let answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+    let calendar = Calendar::for_year(year);
+
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            if run_all {
+                calendar.days().collect()
+            } else {
+                // when the `--all` flag is not set, only verify days that have recorded answers.
+                answers.data.iter().map(|a| a.day).collect()
+            }
+        },
+        |day| HashSet::from([day]),
+    );
+
+    let options = MultiRunOptions {
+        is_release: true,
+        timeout,
+        ..MultiRunOptions::default()
+    };
+    let runs = run_days(year, &days_to_run, &options);
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    for run in &runs {
+        let results = run.results.as_deref().unwrap_or_default();
+        let failed_before = failed;
+
+        // a changed input explains failing answers, so it is reported before them.
+        let input_failed = match inputs::check(Puzzle::new(year, run.day)) {
+            Ok(Integrity::Ok | Integrity::Unrecorded) => false,
+            Ok(Integrity::Mismatch { expected, actual }) => {
+                let short = |checksum: &str| checksum.get(..8).unwrap_or(checksum).to_string();
+                println!(
+                    "Day {}: ✖ input changed (checksum {}…, expected {}…)",
+                    run.day,
+                    short(&actual),
+                    short(&expected)
+                );
+                true
+            }
+            Err(e) => {
+                println!("Day {}: ✖ could not check input: {e}", run.day);
+                true
+            }
+        };
+
+        for result in results.iter().filter(|r| calendar.has_part(r.day, r.part)) {
+            let label = format!("Day {} Part {}", result.day, result.part);
+            match answers.verify(result) {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("{label}: ✔ pass");
+                }
+                Verdict::Fail { expected, actual } => {
+                    failed += 1;
+                    let actual = actual.unwrap_or_else(|| "nothing".into());
+                    println!("{label}: ✖ fail (expected {expected}, got {actual})");
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    println!("{label}: ? missing");
+                }
+            }
+        }
+
+        // parts with a recorded answer that produced no result at all, e.g. because they were
+        // removed or their day crashed before reaching them.
+        for part in [1, 2]
+            .into_iter()
+            .filter(|p| calendar.has_part(run.day, *p))
+        {
+            if results.iter().any(|r| r.part == part) {
+                continue;
+            }
+            if let Some(expected) = answers.get(run.day, part) {
+                failed += 1;
+                println!(
+                    "Day {} Part {part}: ✖ fail (expected {expected}, got nothing)",
+                    run.day
+                );
+            }
+        }
+
+        match &run.status {
+            DayStatus::Ok => {}
+            DayStatus::NotSolved => println!("Day {}: not solved", run.day),
+            // a crash or timeout fails the day, unless one of its answers already did.
+            status => {
+                println!("Day {}: {status}", run.day);
+                if failed == failed_before {
+                    failed += 1;
+                }
+            }
+        }
+
+        if input_failed && failed == failed_before {
+            failed += 1;
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {missing} missing.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
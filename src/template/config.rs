@@ -0,0 +1,470 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+/// Every setting is optional. Environment variables, e.g. `AOC_YEAR`, take precedence over the file.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use toml::{Table, Value};
+
+use crate::template::runner::BenchOptions;
+use crate::template::{paths, FIRST_YEAR};
+
+pub const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+/// Marker that delimits the benchmark table in the readme.
+pub const DEFAULT_README_MARKER: &str = "<!--- benchmarking table --->";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Year that commands work on, unless `--year` is passed.
+    pub year: Option<u16>,
+    /// Directory holding inputs, examples, answers and timings, relative to the project root.
+    pub data_dir: Option<PathBuf>,
+    /// Default bench options, `--bench-time` and `--min-samples` take precedence.
+    pub bench: BenchOptions,
+    pub submit: SubmitConfig,
+    pub readme: ReadmeConfig,
+    pub event: EventConfig,
+    pub inputs: InputsConfig,
+    pub run: RunConfig,
+}
+
+/// Policy for submitting answers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmitConfig {
+    /// Check answers against the submission history before submitting them.
+    pub guard: bool,
+    /// Record accepted answers in `answers.json`, as if `--record` was passed.
+    pub record: bool,
+}
+
+/// Location of the benchmark table updated by `cargo time --store`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadmeConfig {
+    /// File containing the table, relative to the project root.
+    pub file: PathBuf,
+    pub marker: String,
+}
+
+/// Storage of puzzle inputs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputsConfig {
+    /// Keep inputs encrypted at rest, e.g. to commit them to a public repository.
+    pub encrypt: bool,
+    /// File holding the key inputs are encrypted with, relative to the project root.
+    pub key_file: PathBuf,
+}
+
+/// Defaults for running the solutions of multiple days.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunConfig {
+    /// Stop days that do not finish within this duration, unless `--timeout` is passed.
+    pub timeout: Option<Duration>,
+}
+
+/// Overrides for the event of the configured year.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventConfig {
+    pub days: Option<u8>,
+    pub final_day_part_two: Option<bool>,
+    /// Offset of the Advent of Code server from UTC in hours, puzzles unlock at midnight there.
+    pub utc_offset: i32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            data_dir: None,
+            bench: BenchOptions::DEFAULT,
+            submit: SubmitConfig {
+                guard: true,
+                record: false,
+            },
+            readme: ReadmeConfig {
+                file: "README.md".into(),
+                marker: DEFAULT_README_MARKER.into(),
+            },
+            event: EventConfig {
+                days: None,
+                final_day_part_two: None,
+                utc_offset: -5,
+            },
+            inputs: InputsConfig {
+                encrypt: false,
+                key_file: ".aoc-key".into(),
+            },
+            run: RunConfig { timeout: None },
+        }
+    }
+}
+
+/// An error in the configuration file, along with the line it occurred on.
+#[derive(Debug, PartialEq)]
+pub struct ConfigError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{CONFIG_FILE_NAME}:{line}: {}", self.message),
+            None => write!(f, "{CONFIG_FILE_NAME}: {}", self.message),
+        }
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The configuration of the project. Exits the process if the configuration file is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::read_from_file(&paths::root().join(CONFIG_FILE_NAME)).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        })
+    })
+}
+
+impl Config {
+    /// Read the configuration from `path`. If not present, returns the default configuration.
+    pub fn read_from_file(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError {
+                line: None,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+        let document: Table = text.parse().map_err(|e| ConfigError::syntax(text, &e))?;
+
+        // settings before the first section belong to the section `""`.
+        let mut settings = vec![];
+        for (key, value) in document {
+            match value {
+                Value::Table(table) if SECTIONS.contains(&key.as_str()) => {
+                    settings.extend(table.into_iter().map(|(k, value)| (key.clone(), k, value)));
+                }
+                Value::Table(_) => {
+                    return Err(ConfigError {
+                        line: section_line(text, &key),
+                        message: format!("`[{key}]` is not a known section."),
+                    });
+                }
+                value => settings.push((String::new(), key, value)),
+            }
+        }
+
+        for (section, key, value) in settings {
+            let error = |message: &str| ConfigError {
+                line: key_line(text, &section, &key),
+                message: format!("`{}` {message}", qualified_key(&section, &key)),
+            };
+
+            match (section.as_str(), key.as_str()) {
+                ("", "year") => {
+                    let year = value
+                        .as_integer()
+                        .and_then(|year| u16::try_from(year).ok())
+                        .filter(|year| *year >= FIRST_YEAR)
+                        .ok_or_else(|| {
+                            error(&format!("must be a year not before {FIRST_YEAR}."))
+                        })?;
+                    config.year = Some(year);
+                }
+                ("paths", "data") => {
+                    let dir = value
+                        .as_str()
+                        .filter(|dir| !dir.is_empty())
+                        .ok_or_else(|| error("must be a non-empty string."))?;
+                    config.data_dir = Some(dir.into());
+                }
+                ("bench", "time") => {
+                    config.bench.time = number(&value)
+                        .filter(|secs| *secs > 0.0)
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                        .ok_or_else(|| error("must be a positive number of seconds."))?;
+                }
+                ("bench", "min_samples") => {
+                    config.bench.min_samples = positive_integer(&value)
+                        .ok_or_else(|| error("must be a positive integer."))?;
+                }
+                ("bench", "max_samples") => {
+                    config.bench.max_samples = positive_integer(&value)
+                        .ok_or_else(|| error("must be a positive integer."))?;
+                }
+                ("submit", "guard") => {
+                    config.submit.guard =
+                        value.as_bool().ok_or_else(|| error("must be a boolean."))?;
+                }
+                ("submit", "record") => {
+                    config.submit.record =
+                        value.as_bool().ok_or_else(|| error("must be a boolean."))?;
+                }
+                ("readme", "file") => {
+                    let file = value
+                        .as_str()
+                        .filter(|file| !file.is_empty())
+                        .ok_or_else(|| error("must be a non-empty string."))?;
+                    config.readme.file = file.into();
+                }
+                ("readme", "marker") => {
+                    let marker = value
+                        .as_str()
+                        .filter(|marker| !marker.trim().is_empty() && !marker.contains('\n'))
+                        .ok_or_else(|| error("must be a non-empty, single-line string."))?;
+                    config.readme.marker = marker.into();
+                }
+                ("event", "days") => {
+                    let days = value
+                        .as_integer()
+                        .filter(|days| (1..=25).contains(days))
+                        .ok_or_else(|| error("must be an integer between 1 and 25."))?;
+                    config.event.days = u8::try_from(days).ok();
+                }
+                ("event", "final_day_part_two") => {
+                    config.event.final_day_part_two =
+                        Some(value.as_bool().ok_or_else(|| error("must be a boolean."))?);
+                }
+                ("event", "utc_offset") => {
+                    let offset = value
+                        .as_integer()
+                        .filter(|offset| (-12..=14).contains(offset))
+                        .ok_or_else(|| error("must be an integer between -12 and 14."))?;
+                    config.event.utc_offset = i32::try_from(offset).unwrap_or_default();
+                }
+                ("inputs", "encrypt") => {
+                    config.inputs.encrypt =
+                        value.as_bool().ok_or_else(|| error("must be a boolean."))?;
+                }
+                ("inputs", "key_file") => {
+                    let file = value
+                        .as_str()
+                        .filter(|file| !file.is_empty())
+                        .ok_or_else(|| error("must be a non-empty string."))?;
+                    config.inputs.key_file = file.into();
+                }
+                ("run", "timeout") => {
+                    let timeout = number(&value)
+                        .filter(|secs| *secs > 0.0)
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                        .ok_or_else(|| error("must be a positive number of seconds."))?;
+                    config.run.timeout = Some(timeout);
+                }
+                _ => return Err(error("is not a known setting.")),
+            }
+        }
+
+        if config.bench.max_samples < config.bench.min_samples {
+            return Err(ConfigError {
+                line: key_line(text, "bench", "max_samples"),
+                message: "`bench.max_samples` must not be less than `bench.min_samples`.".into(),
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+const SECTIONS: [&str; 7] = [
+    "paths", "bench", "submit", "readme", "event", "inputs", "run",
+];
+
+impl ConfigError {
+    /// A syntax error reported by the TOML parser, located on its line in `text`.
+    pub fn syntax(text: &str, e: &toml::de::Error) -> Self {
+        ConfigError {
+            line: e.span().map(|span| line_of(text, span.start)),
+            message: e.message().trim_end_matches('\n').into(),
+        }
+    }
+}
+
+/// The line of the byte offset `at` in `text`, starting at 1.
+pub fn line_of(text: &str, at: usize) -> usize {
+    text.get(..at).unwrap_or(text).matches('\n').count() + 1
+}
+
+/// The line `key` of `section` is set on, starting at 1. Settings before the first section belong
+/// to the section `""`, and may also set a key of another section with a dotted key, e.g.
+/// `bench.time = 1`. Returns `None` if the key can not be found, e.g. in an inline table.
+fn key_line(text: &str, section: &str, key: &str) -> Option<usize> {
+    let qualified = qualified_key(section, key);
+    let mut current = "";
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = table_header(line) {
+            current = header;
+        } else if let Some((k, _)) = line.split_once('=') {
+            let k = k.trim().trim_matches(['"', '\'']);
+            if (current == section && k == key) || (current.is_empty() && k == qualified) {
+                return Some(i + 1);
+            }
+        }
+    }
+
+    None
+}
+
+/// The line the header of `section` is on, starting at 1.
+fn section_line(text: &str, section: &str) -> Option<usize> {
+    text.lines()
+        .position(|line| table_header(line.trim()) == Some(section))
+        .map(|i| i + 1)
+}
+
+/// The name of the table a line starts, e.g. `bench` for `[bench] # comment`.
+fn table_header(line: &str) -> Option<&str> {
+    let (header, _) = line.strip_prefix('[')?.split_once(']')?;
+    Some(header.trim())
+}
+
+/// The key of a setting including its section, e.g. `bench.time`.
+pub fn qualified_key(section: &str, key: &str) -> String {
+    if section.is_empty() {
+        key.into()
+    } else {
+        format!("{section}.{key}")
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(x) => Some(*x as f64),
+        Value::Float(x) => Some(*x),
+        _ => None,
+    }
+}
+
+fn positive_integer(value: &Value) -> Option<u128> {
+    value
+        .as_integer()
+        .filter(|x| *x > 0)
+        .and_then(|x| u128::try_from(x).ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, ConfigError};
+    use std::time::Duration;
+
+    fn error(text: &str) -> ConfigError {
+        Config::parse(text).unwrap_err()
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+# Advent of Code settings
+year = 2023
+
+[paths]
+data = "private/data" # inputs are stored in a submodule
+
+[bench]
+time = 0.5
+min_samples = 5
+max_samples = 1_000
+
+[submit]
+guard = false
+
+[readme]
+marker = "<!-- \"bench\" -->"
+
+[event]
+days = 12
+
+[run]
+timeout = 30
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.data_dir, Some("private/data".into()));
+        assert_eq!(config.bench.time, Duration::from_millis(500));
+        assert_eq!(config.bench.min_samples, 5);
+        assert_eq!(config.bench.max_samples, 1000);
+        assert!(!config.submit.guard);
+        assert!(!config.submit.record);
+        assert_eq!(config.readme.file, std::path::PathBuf::from("README.md"));
+        assert_eq!(config.readme.marker, "<!-- \"bench\" -->");
+        assert_eq!(config.event.days, Some(12));
+        assert_eq!(config.event.utc_offset, -5);
+        assert_eq!(config.run.timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn defaults_to_empty_config() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(
+            error("[bench]\ntime = \"fast\"").to_string(),
+            "aoc.toml:2: `bench.time` must be a positive number of seconds."
+        );
+        assert_eq!(
+            error("year = 2014").to_string(),
+            "aoc.toml:1: `year` must be a year not before 2015."
+        );
+        assert_eq!(
+            error("\n[benchmarks]").to_string(),
+            "aoc.toml:2: `[benchmarks]` is not a known section."
+        );
+        assert_eq!(
+            error("[submit]\nforce = true").to_string(),
+            "aoc.toml:2: `submit.force` is not a known setting."
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_lines() {
+        assert_eq!(error("year = 2024\nyear = 2023").line, Some(2));
+        assert_eq!(error("[readme]\nfile = \"README.md").line, Some(2));
+        assert_eq!(error("year").line, Some(1));
+        assert_eq!(error("\n[bench").line, Some(2));
+    }
+
+    #[test]
+    fn reports_errors_with_lines() {
+        assert_eq!(error("# settings\nyear = 2014").line, Some(2));
+        assert_eq!(
+            error("year = 2024\n\n[bench]\nmin_samples = 1\ntime = -1").line,
+            Some(5)
+        );
+        assert_eq!(error("bench.time = 0").line, Some(1));
+        assert_eq!(error("[paths]\n\n[benchmarks]\ntime = 1").line, Some(3));
+        assert_eq!(
+            error("[readme]\nfile = \"\"").to_string(),
+            "aoc.toml:2: `readme.file` must be a non-empty string."
+        );
+        assert_eq!(error("[bench]\nmax_samples = 1").line, Some(2));
+    }
+
+    #[test]
+    fn validates_sample_bounds() {
+        assert_eq!(
+            error("[bench]\nmin_samples = 100\nmax_samples = 10").to_string(),
+            "aoc.toml:3: `bench.max_samples` must not be less than `bench.min_samples`."
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::env;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -5,9 +6,6 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
-
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -47,7 +45,7 @@ impl Day {
 impl Day {
     /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let offset = FixedOffset::east_opt(super::config::get().event.utc_offset * 3600)?;
         let today = Utc::now().with_timezone(&offset);
         if today.month() == 12 && today.day() <= 25 {
             Self::new(u8::try_from(today.day()).ok()?)
@@ -100,20 +98,183 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// The first year of advent.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// A puzzle of a specific year of advent.
+///
+/// # Display
+/// This value displays as the year followed by the two digit day number, which is also the name
+/// of the solution binary of the puzzle.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle};
+/// let puzzle = Puzzle::new(2024, Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: u16, day: Day) -> Self {
+        Self { year, day }
+    }
+}
+
+#[cfg(feature = "today")]
+impl Puzzle {
+    /// Returns the puzzle of the current day if it's part of this year's calendar, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let year = current_year()?;
+        let day = Day::today()?;
+        Calendar::for_year(year)
+            .contains(day)
+            .then_some(Self::new(year, day))
+    }
+}
+
+/// Returns the current year in the timezone of the event.
+#[cfg(feature = "today")]
+pub fn current_year() -> Option<u16> {
+    let offset = FixedOffset::east_opt(super::config::get().event.utc_offset * 3600)?;
+    u16::try_from(Utc::now().with_timezone(&offset).year()).ok()
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = PuzzleFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('-').ok_or(PuzzleFromStrError)?;
+        let year = parse_year(year).ok_or(PuzzleFromStrError)?;
+        let day = day.parse().map_err(|_| PuzzleFromStrError)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+/// Parses a year of advent, i.e. an integer not before [`FIRST_YEAR`].
+pub fn parse_year(s: &str) -> Option<u16> {
+    s.parse().ok().filter(|year| *year >= FIRST_YEAR)
+}
+
+/// An error which can be returned when parsing a [`Puzzle`].
+#[derive(Debug)]
+pub struct PuzzleFromStrError;
+
+impl Error for PuzzleFromStrError {}
+
+impl Display for PuzzleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle of the form `<year>-<day>`, e.g. `2024-01`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The shape of the calendar of an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    /// Number of days of the event, counting from the 1st of december.
+    pub days: u8,
+    /// Whether the puzzle of the final day has a second part.
+    pub final_day_part_two: bool,
+}
+
+impl Calendar {
+    /// Returns the calendar of the event of `year`.
+    ///
+    /// Events up to 2024 span 25 days, later events span 12 days. The final day of an event only
+    /// has a single part. For the default year, the number of days and whether the final day has a
+    /// second part can be overridden with `AOC_DAYS` and `AOC_FINAL_DAY_PART_TWO`, or in the
+    /// `[event]` section of `aoc.toml`.
+    pub fn for_year(year: u16) -> Self {
+        let mut calendar = Self::default_for_year(year);
+
+        if super::default_year() == Some(year) {
+            let event = &super::config::get().event;
+            if let Some(days) = env::var("AOC_DAYS")
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .or(event.days)
+            {
+                calendar.days = days;
+            }
+            if let Some(part_two) = env::var("AOC_FINAL_DAY_PART_TWO")
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .or(event.final_day_part_two)
+            {
+                calendar.final_day_part_two = part_two;
+            }
+        }
+
+        calendar.days = calendar.days.clamp(1, 25);
+        calendar
+    }
+
+    /// Returns the calendar of the event of `year`, ignoring overrides.
+    pub const fn default_for_year(year: u16) -> Self {
+        Self {
+            days: if year < 2025 { 25 } else { 12 },
+            final_day_part_two: false,
+        }
+    }
+
+    /// Whether `day` is part of this calendar.
+    pub fn contains(&self, day: Day) -> bool {
+        day <= self.days
+    }
+
+    /// Whether `day` has a puzzle for `part`.
+    pub fn has_part(&self, day: Day, part: u8) -> bool {
+        self.contains(day)
+            && match part {
+                1 => true,
+                2 => day != self.days || self.final_day_part_two,
+                _ => false,
+            }
+    }
+
+    /// An iterator that yields every day of this calendar.
+    pub fn days(&self) -> AllDays {
+        AllDays::until(self.days)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the 25th, or the last day of a
+/// shorter calendar.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(25)
+    }
+
+    /// An iterator that stops after `last`, which is capped to the 25th.
+    pub fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(25),
+        }
     }
 }
 
@@ -121,7 +282,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -154,7 +315,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Calendar, Day, Puzzle};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +348,44 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn calendar_days() {
+        let calendar = Calendar {
+            days: 12,
+            final_day_part_two: false,
+        };
+        assert_eq!(calendar.days().count(), 12);
+        assert_eq!(calendar.days().last(), Some(Day(12)));
+        assert!(calendar.contains(Day(12)));
+        assert!(!calendar.contains(Day(13)));
+    }
+
+    #[test]
+    fn calendar_parts() {
+        let calendar = Calendar::default_for_year(2024);
+        assert!(calendar.has_part(Day(24), 2));
+        assert!(calendar.has_part(Day(25), 1));
+        assert!(!calendar.has_part(Day(25), 2));
+
+        let calendar = Calendar {
+            days: 12,
+            final_day_part_two: true,
+        };
+        assert!(calendar.has_part(Day(12), 2));
+        assert!(!calendar.has_part(Day(13), 1));
+    }
+
+    #[test]
+    fn parses_puzzles() {
+        let puzzle: Puzzle = "2023-07".parse().unwrap();
+        assert_eq!(puzzle, Puzzle::new(2023, Day(7)));
+        assert_eq!(puzzle.to_string(), "2023-07");
+
+        assert!("07".parse::<Puzzle>().is_err());
+        assert!("2023-26".parse::<Puzzle>().is_err());
+        assert!("2014-01".parse::<Puzzle>().is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
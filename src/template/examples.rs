@@ -0,0 +1,343 @@
+/// Examples of a puzzle and the answers expected for them, listed in a manifest next to the example
+/// files, e.g. `data/2024/examples/16.toml`:
+///
+/// ```toml
+/// [first]
+/// part_1 = 7036
+/// part_2 = 45
+///
+/// [second]
+/// file = "16-2.txt"
+/// part_1 = 11048
+/// part_2 = 64
+/// ```
+///
+/// Any other key of an example sets a parameter of the solution, e.g. `width = 11`.
+///
+/// `build.rs` generates a test for every answer in the manifest, which runs the solution against
+/// the example with [`check`].
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+use crate::template::config::{qualified_key, ConfigError};
+use crate::template::runner::{OutputFormat, PartResult, RunOptions};
+use crate::template::{paths, Puzzle};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    /// The example file, relative to the examples directory. Defaults to `{day}.txt`.
+    pub file: PathBuf,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// [Parameters](crate::template::runner::Params) of the solution for this example, e.g. the
+    /// size of its grid. Every other key of the example is a parameter.
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    /// The answer expected for `part`, if any.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    /// The manifest of `puzzle`, e.g. `data/2024/examples/16.toml`.
+    #[must_use]
+    pub fn path(puzzle: Puzzle) -> PathBuf {
+        examples_dir(puzzle).join(format!("{}.toml", puzzle.day))
+    }
+
+    /// Read the manifest of `puzzle`. If not present, returns no examples.
+    pub fn read(puzzle: Puzzle) -> Result<Self, String> {
+        let path = Manifest::path(puzzle);
+        match fs::read_to_string(&path) {
+            Ok(text) => Manifest::parse(&text, puzzle)
+                .map_err(|e| format!("{}: {e}", paths::display(&path))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Parse a manifest. Syntax errors are prefixed with the line they occurred on, e.g. `3: ...`.
+    pub fn parse(text: &str, puzzle: Puzzle) -> Result<Self, String> {
+        let document: Table = text.parse().map_err(|e| {
+            let e = ConfigError::syntax(text, &e);
+            format!("{}: {}", e.line.unwrap_or(1), e.message)
+        })?;
+        let mut examples = vec![];
+
+        for (name, value) in document {
+            let Value::Table(table) = value else {
+                return Err(format!(
+                    "`{name}` must be part of an example, e.g. `[example]`."
+                ));
+            };
+
+            let mut example = Example {
+                file: format!("{}.txt", puzzle.day).into(),
+                part_1: None,
+                part_2: None,
+                params: vec![],
+                name,
+            };
+
+            for (key, value) in table {
+                let error =
+                    |message: &str| format!("`{}` {message}", qualified_key(&example.name, &key));
+
+                match key.as_str() {
+                    "file" => {
+                        example.file = value
+                            .as_str()
+                            .filter(|file| !file.is_empty())
+                            .ok_or_else(|| error("must be a non-empty string."))?
+                            .into();
+                    }
+                    "part_1" | "part_2" => {
+                        let answer = match value {
+                            Value::String(answer) => answer,
+                            Value::Integer(answer) => answer.to_string(),
+                            _ => return Err(error("must be a string or an integer.")),
+                        };
+                        if key == "part_1" {
+                            example.part_1 = Some(answer);
+                        } else {
+                            example.part_2 = Some(answer);
+                        }
+                    }
+                    _ => {
+                        let value = match value {
+                            Value::String(value) => value,
+                            Value::Integer(value) => value.to_string(),
+                            Value::Float(value) => value.to_string(),
+                            Value::Boolean(value) => value.to_string(),
+                            _ => return Err(error("must be a string, number or boolean.")),
+                        };
+                        example.params.push((key, value));
+                    }
+                }
+            }
+
+            examples.push(example);
+        }
+
+        Ok(Manifest { examples })
+    }
+
+    /// Format the manifest of `puzzle` as TOML, omitting default files.
+    pub fn to_toml(&self, puzzle: Puzzle) -> String {
+        let default_file = PathBuf::from(format!("{}.txt", puzzle.day));
+        let mut sections = vec![];
+
+        for example in &self.examples {
+            let mut lines = vec![format!("[{}]", toml_key(&example.name))];
+            if example.file != default_file {
+                let file = example.file.display().to_string();
+                lines.push(format!("file = {}", toml_string(&file)));
+            }
+            let answers = [("part_1", &example.part_1), ("part_2", &example.part_2)];
+            let params = example
+                .params
+                .iter()
+                .map(|(key, value)| (key.as_str(), value));
+            for (key, value) in answers
+                .into_iter()
+                .filter_map(|(key, value)| Some((key, value.as_ref()?)))
+                .chain(params)
+            {
+                // answers like `007` keep their leading zeros as strings.
+                let value = match value.parse::<i64>() {
+                    Ok(x) if x.to_string() == *value => x.to_string(),
+                    _ => toml_string(value),
+                };
+                lines.push(format!("{} = {value}", toml_key(key)));
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        let mut text = sections.join("\n\n");
+        text.push('\n');
+        text
+    }
+
+    /// Whether any example has an expected answer, i.e. the manifest generates tests.
+    pub fn has_answers(&self) -> bool {
+        self.examples
+            .iter()
+            .any(|example| example.part_1.is_some() || example.part_2.is_some())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Example> {
+        self.examples.iter().find(|example| example.name == name)
+    }
+}
+
+fn toml_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A key or table name, quoted unless it is a bare key, e.g. `small` but `"1.5 bytes"`.
+fn toml_key(s: &str) -> String {
+    let is_bare = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_bare {
+        s.into()
+    } else {
+        toml_string(s)
+    }
+}
+
+/// The directory holding the example files and manifest of `puzzle`.
+#[must_use]
+pub fn examples_dir(puzzle: Puzzle) -> PathBuf {
+    paths::year_dir(puzzle.year).join("examples")
+}
+
+/// Run `part` of a solution against the example `name` of its manifest, and assert that it returns
+/// the expected answer. Called by the tests `build.rs` generates.
+pub fn check(puzzle: Puzzle, name: &str, part: u8, run: fn(&str, &RunOptions) -> Vec<PartResult>) {
+    let manifest = Manifest::read(puzzle).unwrap_or_else(|e| panic!("{e}"));
+    let example = manifest.get(name).unwrap_or_else(|| {
+        panic!(
+            "no example `{name}` in \"{}\".",
+            paths::display(&Manifest::path(puzzle))
+        )
+    });
+    let expected = example
+        .answer(part)
+        .unwrap_or_else(|| panic!("example `{name}` has no answer for part {part}."));
+
+    let path = examples_dir(puzzle).join(&example.file);
+    let input = read_example(&path);
+
+    let options = RunOptions {
+        format: OutputFormat::Silent,
+        part: Some(part),
+        params: example.params.clone(),
+        ..RunOptions::default()
+    };
+    let results = run(&input, &options);
+
+    if let Some(error) = results.iter().find_map(|result| result.error.as_ref()) {
+        panic!("example `{name}` failed: {error}");
+    }
+
+    let answer = results
+        .iter()
+        .find(|result| result.part == part)
+        .and_then(|result| result.answer.as_deref());
+    assert_eq!(
+        answer,
+        Some(expected),
+        "wrong answer for part {part} of example `{name}`"
+    );
+}
+
+fn read_example(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "could not open example file \"{}\": {e}",
+            paths::display(path)
+        )
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Manifest;
+    use crate::{day, template::Puzzle};
+    use std::path::PathBuf;
+
+    const PUZZLE: Puzzle = Puzzle::new(2024, day!(16));
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = Manifest::parse(
+            "# examples of day 16\n[small]\npart_1 = 7036\n\n[large]\nfile = \"16-2.txt\"\npart_2 = \"64\"\n",
+            PUZZLE,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.examples.len(), 2);
+        let small = manifest.get("small").unwrap();
+        assert_eq!(small.file, PathBuf::from("16.txt"));
+        assert_eq!(small.answer(1), Some("7036"));
+        assert_eq!(small.answer(2), None);
+        let large = manifest.get("large").unwrap();
+        assert_eq!(large.file, PathBuf::from("16-2.txt"));
+        assert_eq!(large.answer(2), Some("64"));
+    }
+
+    #[test]
+    fn formats_manifests() {
+        let text = "[first]\npart_1 = 7036\n\n[second]\nfile = \"16-2.txt\"\npart_2 = \"4,\\\"6\\\"\"\nwidth = 7\n";
+        let manifest = Manifest::parse(text, PUZZLE).unwrap();
+        assert_eq!(manifest.to_toml(PUZZLE), text);
+
+        // names that are not bare keys are quoted, so they round-trip instead of nesting tables.
+        let text = "[\"1.5 \\\"big\\\"\"]\npart_1 = 1\n\n[\"\"]\n\"max.steps\" = 3\n";
+        let manifest = Manifest::parse(text, PUZZLE).unwrap();
+        assert_eq!(manifest.examples[0].name, "1.5 \"big\"");
+        assert_eq!(manifest.to_toml(PUZZLE), text);
+        let reparsed = Manifest::parse(&manifest.to_toml(PUZZLE), PUZZLE).unwrap();
+        assert_eq!(reparsed.examples, manifest.examples);
+    }
+
+    #[test]
+    fn parses_params() {
+        let manifest =
+            Manifest::parse("[small]\nwidth = 11\npart_1 = 12\nlabel = \"a\"\n", PUZZLE).unwrap();
+
+        let small = manifest.get("small").unwrap();
+        assert_eq!(small.answer(1), Some("12"));
+        assert_eq!(
+            small.params,
+            [("width".into(), "11".into()), ("label".into(), "a".into())]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let error = |text: &str| Manifest::parse(text, PUZZLE).unwrap_err();
+        assert_eq!(
+            error("part_1 = 1"),
+            "`part_1` must be part of an example, e.g. `[example]`."
+        );
+        assert_eq!(
+            error("[small]\npart_1 = true"),
+            "`small.part_1` must be a string or an integer."
+        );
+        assert!(error("[small]\npart_1 = 1\n[small]").starts_with("3: "));
+    }
+}
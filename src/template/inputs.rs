@@ -0,0 +1,471 @@
+/// Storage of puzzle inputs in `data/{year}/inputs`.
+/// Inputs are plain text files by default. With `encrypt = true` in the `[inputs]` section of
+/// `aoc.toml`, they are kept encrypted at rest with a local key instead, e.g. `01.txt.enc`, and
+/// decrypted transparently when read. A plain text input takes precedence over an encrypted one.
+///
+/// Checksums of inputs are recorded in `data/{year}/checksums.json`, so `cargo verify` can detect
+/// inputs that were truncated or replaced by accident.
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::{Display, Write as _},
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    digest,
+    rand::{SecureRandom, SystemRandom},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{config, paths, Day, Puzzle};
+
+const CHECKSUMS_FILE_NAME: &str = "checksums.json";
+const ENCRYPTED_EXTENSION: &str = "txt.enc";
+/// Header of encrypted inputs, followed by the nonce and the ciphertext.
+const MAGIC: &[u8] = b"AOCENC01";
+const KEY_LEN: usize = 32;
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(PathBuf),
+    MissingKey(PathBuf),
+    InvalidKey(String),
+    /// The input was encrypted with another key, or modified after it was encrypted.
+    Decrypt(PathBuf),
+    Encoding(PathBuf),
+    /// The recorded checksums can not be read or parsed.
+    Checksums(PathBuf, String),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(path) => {
+                write!(f, "could not open input file \"{}\"", paths::display(path))
+            }
+            Error::MissingKey(path) => write!(
+                f,
+                "no key to decrypt inputs. Set `AOC_INPUT_KEY`, or restore the key file \"{}\".",
+                paths::display(path)
+            ),
+            Error::InvalidKey(source) => write!(
+                f,
+                "invalid key in {source}, expected {} hexadecimal characters.",
+                KEY_LEN * 2
+            ),
+            Error::Decrypt(path) => write!(
+                f,
+                "could not decrypt \"{}\": the key does not match, or the file was modified.",
+                paths::display(path)
+            ),
+            Error::Encoding(path) => write!(
+                f,
+                "input file \"{}\" is not valid UTF-8.",
+                paths::display(path)
+            ),
+            Error::Checksums(path, e) => write!(
+                f,
+                "could not read checksums \"{}\": {e}",
+                paths::display(path)
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The plain text input of `puzzle`, e.g. `data/2024/inputs/01.txt`.
+#[must_use]
+pub fn plain_path(puzzle: Puzzle) -> PathBuf {
+    paths::data_path("inputs", puzzle)
+}
+
+/// The encrypted input of `puzzle`, e.g. `data/2024/inputs/01.txt.enc`.
+#[must_use]
+pub fn encrypted_path(puzzle: Puzzle) -> PathBuf {
+    plain_path(puzzle).with_extension(ENCRYPTED_EXTENSION)
+}
+
+/// The key file configured with `key_file` in the `[inputs]` section of `aoc.toml`.
+#[must_use]
+pub fn key_path() -> PathBuf {
+    paths::root().join(&config::get().inputs.key_file)
+}
+
+#[must_use]
+pub fn exists(puzzle: Puzzle) -> bool {
+    plain_path(puzzle).exists() || encrypted_path(puzzle).exists()
+}
+
+/// Read the input of `puzzle`, decrypting it if it is only stored encrypted.
+pub fn read(puzzle: Puzzle) -> Result<String, Error> {
+    let plain = plain_path(puzzle);
+    match fs::read_to_string(&plain) {
+        Ok(input) => return Ok(input),
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let path = encrypted_path(puzzle);
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Err(Error::NotFound(plain)),
+        Err(e) => return Err(e.into()),
+    };
+
+    let input = decrypt(&load_key()?, puzzle, data).ok_or_else(|| Error::Decrypt(path.clone()))?;
+    String::from_utf8(input).map_err(|_| Error::Encoding(path))
+}
+
+/// Store the input of `puzzle` as configured in `aoc.toml` and record its checksum.
+/// Returns the path the input was written to.
+pub fn write(puzzle: Puzzle, input: &str) -> Result<PathBuf, Error> {
+    // read the checksums first, an invalid file must not be replaced nor leave an unrecorded input.
+    let mut checksums = Checksums::read_from_file(puzzle.year)?;
+    let path = store(puzzle, input, config::get().inputs.encrypt)?;
+    checksums.set(puzzle.day, input);
+    checksums.store_file(puzzle.year)?;
+    Ok(path)
+}
+
+/// Store the input of `puzzle` encrypted or as plain text, removing the other representation so
+/// only one copy of the input exists.
+pub fn store(puzzle: Puzzle, input: &str, encrypted: bool) -> Result<PathBuf, Error> {
+    let (path, other, contents) = if encrypted {
+        let contents = encrypt(&load_or_create_key()?, puzzle, input)?;
+        (encrypted_path(puzzle), plain_path(puzzle), contents)
+    } else {
+        let contents = input.as_bytes().to_vec();
+        (plain_path(puzzle), encrypted_path(puzzle), contents)
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, contents)?;
+
+    match fs::remove_file(other) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(path),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_key(hex: &str, source: &str) -> Result<LessSafeKey, Error> {
+    let invalid = || Error::InvalidKey(source.into());
+    let hex = hex.trim();
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &bytes).map_err(|_| invalid())?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Load the key from `AOC_INPUT_KEY`, or the configured key file.
+fn load_key() -> Result<LessSafeKey, Error> {
+    if let Ok(hex) = env::var("AOC_INPUT_KEY") {
+        return parse_key(&hex, "`AOC_INPUT_KEY`");
+    }
+
+    let path = key_path();
+    match fs::read_to_string(&path) {
+        Ok(hex) => parse_key(&hex, &format!("\"{}\"", paths::display(&path))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::MissingKey(path)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Load the key, generating a new key file if there is none yet.
+fn load_or_create_key() -> Result<LessSafeKey, Error> {
+    match load_key() {
+        Err(Error::MissingKey(path)) => {
+            let mut bytes = [0; KEY_LEN];
+            SystemRandom::new()
+                .fill(&mut bytes)
+                .map_err(|_| io::Error::other("could not generate a key."))?;
+
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            options.open(&path)?.write_all(to_hex(&bytes).as_bytes())?;
+
+            println!(
+                "Generated a new key in \"{}\". Keep it safe, inputs can not be decrypted without it.",
+                paths::display(&path)
+            );
+            load_key()
+        }
+        key => key,
+    }
+}
+
+/// Encrypt an input. The puzzle is authenticated along with the input, so an encrypted input can
+/// not be swapped for the input of another day.
+fn encrypt(key: &LessSafeKey, puzzle: Puzzle, input: &str) -> Result<Vec<u8>, Error> {
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| io::Error::other("could not generate a nonce."))?;
+
+    let mut ciphertext = input.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(puzzle.to_string().as_bytes()),
+        &mut ciphertext,
+    )
+    .map_err(|_| io::Error::other("could not encrypt input."))?;
+
+    Ok([MAGIC, &nonce, &ciphertext].concat())
+}
+
+fn decrypt(key: &LessSafeKey, puzzle: Puzzle, data: Vec<u8>) -> Option<Vec<u8>> {
+    let data = data.strip_prefix(MAGIC)?;
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+    let mut buffer = ciphertext.to_vec();
+    let len = key
+        .open_in_place(nonce, Aad::from(puzzle.to_string().as_bytes()), &mut buffer)
+        .ok()?
+        .len();
+    buffer.truncate(len);
+    Some(buffer)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut s, byte| {
+        let _ = write!(s, "{byte:02x}");
+        s
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// SHA-256 checksum of an input, as a hexadecimal string.
+#[must_use]
+pub fn checksum(input: &str) -> String {
+    to_hex(digest::digest(&digest::SHA256, input.as_bytes()).as_ref())
+}
+
+/// Outcome of checking an input against its recorded checksum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Integrity {
+    Ok,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// No checksum has been recorded for this input yet.
+    Unrecorded,
+}
+
+/// Recorded checksums of the inputs of a year.
+/// Can be serialized from / to JSON, e.g. `{ "01": "<sha-256>" }`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Checksums {
+    pub data: BTreeMap<Day, String>,
+}
+
+impl Checksums {
+    fn path(year: u16) -> PathBuf {
+        paths::year_dir(year).join(CHECKSUMS_FILE_NAME)
+    }
+
+    /// Dehydrate checksums to `data/{year}/checksums.json`.
+    pub fn store_file(&self, year: u16) -> Result<(), io::Error> {
+        fs::create_dir_all(paths::year_dir(year))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Checksums::path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate checksums from `data/{year}/checksums.json`. If not present, returns no checksums.
+    /// Fails if the file can not be read or parsed, so it is never replaced by the few checksums
+    /// recorded afterwards.
+    pub fn read_from_file(year: u16) -> Result<Self, Error> {
+        Checksums::read(&Checksums::path(year))
+    }
+
+    fn read(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(json) => Checksums::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Checksums::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| Error::Checksums(path.to_path_buf(), e))
+    }
+
+    /// Record the checksum of the input of `day`.
+    pub fn set(&mut self, day: Day, input: &str) {
+        self.data.insert(day, checksum(input));
+    }
+
+    /// Check the input of `day` against its recorded checksum.
+    #[must_use]
+    pub fn check(&self, day: Day, input: &str) -> Integrity {
+        match self.data.get(&day) {
+            None => Integrity::Unrecorded,
+            Some(expected) => {
+                let actual = checksum(input);
+                if *expected == actual {
+                    Integrity::Ok
+                } else {
+                    Integrity::Mismatch {
+                        expected: expected.clone(),
+                        actual,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Check the stored input of `puzzle` against its recorded checksum.
+pub fn check(puzzle: Puzzle) -> Result<Integrity, Error> {
+    let input = read(puzzle)?;
+    Ok(Checksums::read_from_file(puzzle.year)?.check(puzzle.day, &input))
+}
+
+impl From<Checksums> for JsonValue {
+    fn from(value: Checksums) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .data
+            .into_iter()
+            .map(|(day, checksum)| (day.to_string(), JsonValue::String(checksum)))
+            .collect();
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Checksums {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let data = map
+            .iter()
+            .map(|(day, checksum)| {
+                let day = Day::from_str(day).map_err(|_| format!("invalid day `{day}`."))?;
+                let checksum = checksum
+                    .get::<String>()
+                    .ok_or("expected checksum to be a string.")?;
+                Ok((day, checksum.clone()))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Checksums { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, decrypt, encrypt, parse_key, Checksums, Error, Integrity};
+    use crate::{day, template::Puzzle};
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn encrypts_inputs() {
+        let key = parse_key(KEY, "test").unwrap();
+        let puzzle = Puzzle::new(2024, day!(1));
+        let data = encrypt(&key, puzzle, "3   4\n4   3\n").unwrap();
+        assert!(!data.windows(5).any(|w| w == b"3   4"));
+        assert_eq!(
+            decrypt(&key, puzzle, data.clone()).as_deref(),
+            Some(b"3   4\n4   3\n".as_slice())
+        );
+
+        // the input of another day, a modified input or another key are rejected.
+        assert_eq!(
+            decrypt(&key, Puzzle::new(2024, day!(2)), data.clone()),
+            None
+        );
+        let mut modified = data.clone();
+        *modified.last_mut().unwrap() ^= 1;
+        assert_eq!(decrypt(&key, puzzle, modified), None);
+        let other = parse_key(&KEY.replace("00", "ff"), "test").unwrap();
+        assert_eq!(decrypt(&other, puzzle, data), None);
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(matches!(
+            parse_key("abc", "test"),
+            Err(Error::InvalidKey(_))
+        ));
+        assert!(matches!(
+            parse_key(&KEY.replace('0', "g"), "test"),
+            Err(Error::InvalidKey(_))
+        ));
+        assert!(parse_key(&format!("{KEY}\n"), "test").is_ok());
+    }
+
+    #[test]
+    fn checks_inputs_against_checksums() {
+        assert_eq!(
+            checksum("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        let mut checksums = Checksums::default();
+        checksums.set(day!(1), "3   4\n4   3\n");
+        assert_eq!(checksums.check(day!(1), "3   4\n4   3\n"), Integrity::Ok);
+        assert!(matches!(
+            checksums.check(day!(1), "3   4\n"),
+            Integrity::Mismatch { .. }
+        ));
+        assert_eq!(checksums.check(day!(2), ""), Integrity::Unrecorded);
+
+        let json = tinyjson::JsonValue::from(checksums.clone()).stringify();
+        assert_eq!(Checksums::try_from(json.unwrap()).unwrap(), checksums);
+    }
+
+    #[test]
+    fn reads_checksum_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-checksums-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("checksums.json");
+
+        assert!(Checksums::read(&path).unwrap().data.is_empty());
+
+        std::fs::write(&path, r#"{ "01": "abc" }"#).unwrap();
+        assert_eq!(Checksums::read(&path).unwrap().data[&day!(1)], "abc");
+
+        // a corrupt file is an error instead of an empty set of checksums.
+        std::fs::write(&path, r#"{ "01": "abc""#).unwrap();
+        assert!(matches!(Checksums::read(&path), Err(Error::Checksums(..))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,12 +1,21 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod inputs;
+pub mod paths;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
 
 pub use day::*;
+pub use registry::Solution;
 
 mod day;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
@@ -15,54 +24,217 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file in `data/{year}/{folder}` to a string.
+/// Inputs are read from the [input store](inputs), which decrypts encrypted inputs.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file(folder: &str, puzzle: Puzzle) -> String {
+    if folder == "inputs" {
+        return inputs::read(puzzle).unwrap_or_else(|e| panic!("{e}"));
+    }
+    let f = fs::read_to_string(paths::data_path(folder, puzzle));
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file_part(folder: &str, puzzle: Puzzle, part: u8) -> String {
+    let f = fs::read_to_string(paths::data_part_path(folder, puzzle, part));
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// The year commands default to, as configured by the `AOC_YEAR` environment variable or `year`
+/// in `aoc.toml`.
+pub fn default_year() -> Option<u16> {
+    match env::var("AOC_YEAR") {
+        Ok(year) => parse_year(&year),
+        Err(_) => config::get().year,
+    }
+}
+
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The first two parameters are the year and day of the puzzle, e.g. `solution!(2024, 1)`.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A shared parse stage can be declared with `parse = <function>`, e.g. `solution!(2024, 16, parse = parse)`.
+/// Its output is passed by reference to both parts, and the runner times it separately.
+///
+/// If preparing the input can fail, declare a `prepare = <function>` stage returning a `Result` instead.
+/// The parts run against a reference to the prepared input, or are skipped if preparing it failed.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parameters that differ between the examples and the real input, e.g. the size of a grid, are
+/// declared with [`params!`] and passed with `params = <type>` before any other stage, e.g.
+/// `solution!(2024, 14, params = Params)`. Every stage may take a reference to them as its second
+/// argument. Examples override them in their manifest, and `--param <key>=<value>` on the command line.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr $(, params = $params:ty)?) => {
+        $crate::solution!(@impl $year, $day, [$($params)?], [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr $(, params = $params:ty)?, 1) => {
+        $crate::solution!(@impl $year, $day, [$($params)?], [part_one, 1]);
+    };
+    ($year:expr, $day:expr $(, params = $params:ty)?, 2) => {
+        $crate::solution!(@impl $year, $day, [$($params)?], [part_two, 2]);
+    };
+    ($year:expr, $day:expr $(, params = $params:ty)?, parse = $parse:expr) => {
+        $crate::solution!(@impl_parse $year, $day, [$($params)?], $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr $(, params = $params:ty)?, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parse $year, $day, [$($params)?], $parse, [part_one, 1]);
+    };
+    ($year:expr, $day:expr $(, params = $params:ty)?, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parse $year, $day, [$($params)?], $parse, [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr $(, params = $params:ty)?, prepare = $prepare:expr) => {
+        $crate::solution!(@impl_prepare $year, $day, [$($params)?], $prepare, [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr $(, params = $params:ty)?, prepare = $prepare:expr, 1) => {
+        $crate::solution!(@impl_prepare $year, $day, [$($params)?], $prepare, [part_one, 1]);
+    };
+    ($year:expr, $day:expr $(, params = $params:ty)?, prepare = $prepare:expr, 2) => {
+        $crate::solution!(@impl_prepare $year, $day, [$($params)?], $prepare, [part_two, 2]);
+    };
+
+    (@impl $year:expr, $day:expr, $params:tt, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@main $year, $day, $params, input, options, params, {
+            let mut results = vec![];
+            $( if options.runs_part($part) {
+                results.push(run_part($func, input, &params, PUZZLE, $part, options));
+            } )*
+            results
+        });
+    };
+
+    (@impl_parse $year:expr, $day:expr, $params:tt, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@main $year, $day, $params, input, options, params, {
+            let (parsed, parse_result) = run_parse($parse, input, &params, PUZZLE, options);
+            let mut results = vec![parse_result];
+            $( if options.runs_part($part) {
+                results.push(run_part($func, &parsed, &params, PUZZLE, $part, options));
+            } )*
+            results
+        });
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl_prepare $year:expr, $day:expr, $params:tt, $prepare:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@main $year, $day, $params, input, options, params, {
+            match run_prepare($prepare, input, &params, PUZZLE, options) {
+                (Some(prepared), prepare_result) => {
+                    let mut results = vec![prepare_result];
+                    $( if options.runs_part($part) {
+                        results.push(run_part($func, &prepared, &params, PUZZLE, $part, options));
+                    } )*
+                    results
+                }
+                (None, prepare_result) => vec![prepare_result],
+            }
+        });
+    };
+
+    (@params_type []) => { () };
+    (@params_type [$params:ty]) => { $params };
+
+    (@main $year:expr, $day:expr, $params_type:tt, $input:ident, $options:ident, $params:ident, $run:block) => {
+        const _: () = assert!(
+            $year >= $crate::template::FIRST_YEAR,
+            concat!("invalid year `", $year, "`, expecting `solution!(<year>, <day>)`"),
+        );
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new($year, DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
+        /// Registry entry that allows this day to be run in-process.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            puzzle: PUZZLE,
+            run: __run_parts,
+        };
+
+        /// Parameters of the solution, or `()` if it has none.
+        type __Params = $crate::solution!(@params_type $params_type);
+
+        fn __run_parts(
+            $input: &str,
+            $options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::runner::PartResult> {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let $params: __Params = match run_params(PUZZLE, $options) {
+                Ok(params) => params,
+                Err(results) => return results,
+            };
+            $run
+        }
+
+        /// Tests generated by `build.rs` from the examples manifest, e.g. `data/2024/examples/16.toml`.
+        #[cfg(test)]
+        mod examples {
+            include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
+        }
+
+        #[allow(dead_code)]
+        fn main() {
+            let options = $crate::template::runner::RunOptions::from_args(PUZZLE);
+            let input = options.input.read(PUZZLE).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+            let results = __run_parts(&input, &options);
+            $crate::template::runner::print_results(&results, &options);
+        }
+    };
+}
+
+/// Declares the [parameters](runner::Params) of a solution along with their defaults, which apply
+/// to the real input. Values are parsed with `FromStr` when overridden.
+///
+/// ```ignore
+/// advent_of_code::params! {
+///     pub struct Params {
+///         width: isize = 101,
+///         height: isize = 103,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident : $ty:ty = $default:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $( $(#[$field_meta])* pub $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $( $field: $default, )* }
+            }
+        }
+
+        impl $crate::template::runner::Params for $name {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $( stringify!($field) => {
+                        self.$field = value
+                            .parse()
+                            .map_err(|e| format!("invalid value `{value}` for `{key}`: {e}"))?;
+                        Ok(())
+                    } )*
+                    _ => Err(format!("unknown parameter `{key}`.")),
+                }
+            }
         }
     };
 }
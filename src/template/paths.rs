@@ -0,0 +1,118 @@
+/// Resolves the locations of the project and its data, so commands and solution binaries work
+/// regardless of the directory they are invoked from.
+///  1. the project root is `AOC_ROOT`, or the closest directory containing a `Cargo.toml`, starting
+///     from the current directory. Falls back to the directory this crate was built from.
+///  2. the data directory is `AOC_DATA_DIR` or `paths.data` in `aoc.toml`, resolved relative to
+///     the project root, or `data`.
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{config, Puzzle};
+
+const DEFAULT_DATA_DIR: &str = "data";
+
+/// The root directory of the project, containing `Cargo.toml`, `README.md` and `src/bin`.
+#[must_use]
+pub fn root() -> PathBuf {
+    if let Some(root) = env::var_os("AOC_ROOT") {
+        return PathBuf::from(root);
+    }
+
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| find_root(&cwd))
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+}
+
+/// The directory holding the data of every year, e.g. inputs and answers.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    let data_dir = env::var_os("AOC_DATA_DIR")
+        .map(PathBuf::from)
+        .or_else(|| config::get().data_dir.clone())
+        .unwrap_or_else(|| DEFAULT_DATA_DIR.into());
+    root().join(data_dir)
+}
+
+/// The `{data}/{year}` directory that holds inputs, examples and results of a year.
+#[must_use]
+pub fn year_dir(year: u16) -> PathBuf {
+    data_dir().join(year.to_string())
+}
+
+/// The text file for `puzzle` in the `{data}/{year}/{folder}` directory, e.g. `inputs/01.txt`.
+#[must_use]
+pub fn data_path(folder: &str, puzzle: Puzzle) -> PathBuf {
+    year_dir(puzzle.year)
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day))
+}
+
+/// The text file for `puzzle` with a part suffix, e.g. `examples/01-2.txt`.
+#[must_use]
+pub fn data_part_path(folder: &str, puzzle: Puzzle, part: u8) -> PathBuf {
+    year_dir(puzzle.year)
+        .join(folder)
+        .join(format!("{}-{part}.txt", puzzle.day))
+}
+
+/// The markdown description of `puzzle`.
+#[must_use]
+pub fn puzzle_path(puzzle: Puzzle) -> PathBuf {
+    year_dir(puzzle.year)
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day))
+}
+
+/// The source file of the solution of `puzzle`.
+#[must_use]
+pub fn bin_path(puzzle: Puzzle) -> PathBuf {
+    root().join("src").join("bin").join(format!("{puzzle}.rs"))
+}
+
+/// The directory holding the scaffold templates, e.g. `templates/grid.txt`.
+#[must_use]
+pub fn templates_dir() -> PathBuf {
+    root().join("templates")
+}
+
+/// The file holding the benchmark table, `readme.file` in `aoc.toml`.
+#[must_use]
+pub fn readme_path() -> PathBuf {
+    root().join(&config::get().readme.file)
+}
+
+/// Format a path for output, relative to the project root if it is inside of it.
+#[must_use]
+pub fn display(path: &Path) -> String {
+    let root = root();
+    path.strip_prefix(&root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn find_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::find_root;
+    use std::path::Path;
+
+    #[test]
+    fn finds_root_from_subdirectories() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(find_root(root).as_deref(), Some(root));
+        assert_eq!(find_root(&root.join("src/template")).as_deref(), Some(root));
+        assert_eq!(
+            find_root(&root.join("target/release")).as_deref(),
+            Some(root)
+        );
+    }
+}
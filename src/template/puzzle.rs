@@ -0,0 +1,400 @@
+/// Extract the puzzle description from a puzzle page and convert it to markdown.
+/// Keeps the `<article>` elements containing the description of each part, as well as the
+/// "Your puzzle answer was ..." paragraphs of solved parts.
+pub fn description_from_html(html: &str) -> String {
+    let main = html.split_once("<main>").map_or(html, |(_, rest)| {
+        rest.split("</main>").next().unwrap_or(rest)
+    });
+
+    let mut sections: Vec<String> = vec![];
+    let mut rest = main;
+
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find("<p>Your puzzle answer was");
+
+        let (start, end_tag) = match (article, answer) {
+            (Some(a), Some(b)) if b < a => (b, "</p>"),
+            (Some(a), _) => (a, "</article>"),
+            (None, Some(b)) => (b, "</p>"),
+            (None, None) => break,
+        };
+
+        let Some(len) = rest[start..].find(end_tag) else {
+            break;
+        };
+        let end = start + len + end_tag.len();
+
+        let section = to_markdown(&rest[start..end]);
+        if !section.is_empty() {
+            sections.push(section);
+        }
+        rest = &rest[end..];
+    }
+
+    let mut description = sections.join("\n\n");
+    description.push('\n');
+    description
+}
+
+/// Convert the subset of HTML used in puzzle descriptions to markdown.
+fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut link: Option<String> = None;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => {
+                in_code = !is_closing;
+                out.push('`');
+            }
+            // NOTE: examples in `<pre>` blocks are kept verbatim, emphasis is dropped there.
+            ("em", _) if !in_pre => out.push('*'),
+            ("a", false) => {
+                link = attribute(tag, "href").map(str::to_string);
+                if link.is_some() {
+                    out.push('[');
+                }
+            }
+            ("a", true) => {
+                if let Some(href) = link.take() {
+                    out.push_str(&format!("]({href})"));
+                }
+            }
+            ("br", _) if !in_code => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Append text content to `out`, decoding entities and collapsing whitespace outside of `<pre>`.
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    for (i, word) in text.split('\n').enumerate() {
+        if i > 0 && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The title of a markdown puzzle description, e.g. `Day 1: Historian Hysteria`.
+pub fn title(description: &str) -> Option<&str> {
+    description.lines().find_map(|line| {
+        let title = line.strip_prefix("## --- ")?.strip_suffix(" ---")?;
+        title.starts_with("Day ").then_some(title)
+    })
+}
+
+/// An example input found in a puzzle description, along with the answers it yields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExampleProposal {
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Propose examples and their answers from a markdown puzzle description.
+///
+/// The example of a part is the first code block introduced by a paragraph mentioning an
+/// example, its answer the last emphasised code span of the part, e.g. `` `*11*` ``. Part two
+/// shares the example of part one unless it introduces its own. Examples without any answer are
+/// not proposed.
+pub fn propose_examples(description: &str) -> Vec<ExampleProposal> {
+    let (part_one, part_two) = description
+        .split_once("## --- Part Two ---")
+        .unwrap_or((description, ""));
+
+    let first = PartExample::find(part_one, true);
+    let second = PartExample::find(part_two, false);
+
+    let mut proposals = vec![];
+    let mut shared = first.input.map(|input| ExampleProposal {
+        input,
+        part_1: first.answer,
+        part_2: None,
+    });
+
+    match (second.input, &mut shared) {
+        (Some(input), Some(shared)) if input == shared.input => shared.part_2 = second.answer,
+        (Some(input), _) => proposals.push(ExampleProposal {
+            input,
+            part_1: None,
+            part_2: second.answer,
+        }),
+        (None, Some(shared)) => shared.part_2 = second.answer,
+        (None, None) => {}
+    }
+
+    if let Some(shared) = shared {
+        proposals.insert(0, shared);
+    }
+    proposals.retain(|p| p.part_1.is_some() || p.part_2.is_some());
+    proposals
+}
+
+struct PartExample {
+    input: Option<String>,
+    answer: Option<String>,
+}
+
+impl PartExample {
+    /// Find the example and answer of a part. Without a paragraph mentioning an example, the
+    /// first code block is used if `fallback` is set.
+    fn find(markdown: &str, fallback: bool) -> Self {
+        let mut input = None;
+        let mut first_block = None;
+        let mut answer = None;
+        let mut introduced = false;
+        let mut block: Option<String> = None;
+
+        for line in markdown.lines() {
+            if line == "```" {
+                match block.take() {
+                    Some(code) => {
+                        if introduced && input.is_none() {
+                            input = Some(code.clone());
+                        }
+                        first_block.get_or_insert(code);
+                    }
+                    None => block = Some(String::new()),
+                }
+                continue;
+            }
+
+            if let Some(code) = &mut block {
+                code.push_str(line);
+                code.push('\n');
+                continue;
+            }
+
+            if !line.trim().is_empty() {
+                introduced = line.to_lowercase().contains("example");
+            }
+            if let Some(last) = emphasised_code(line).last() {
+                answer = Some(last.to_string());
+            }
+        }
+
+        PartExample {
+            input: input.or(first_block.filter(|_| fallback)),
+            answer,
+        }
+    }
+}
+
+/// Code spans of a line that are emphasised, e.g. `` `*11*` `` or `` *`11`* ``.
+fn emphasised_code(line: &str) -> Vec<&str> {
+    let mut spans = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['`', '*']) {
+        let (open, close) = if rest[start..].starts_with("`*") {
+            ("`*", "*`")
+        } else if rest[start..].starts_with("*`") {
+            ("*`", "`*")
+        } else {
+            rest = &rest[start + 1..];
+            continue;
+        };
+
+        let inner = &rest[start + open.len()..];
+        match inner.find(close) {
+            Some(end) => {
+                if !inner[..end].trim().is_empty() {
+                    spans.push(inner[..end].trim());
+                }
+                rest = &inner[end + close.len()..];
+            }
+            None => break,
+        }
+    }
+
+    spans
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{description_from_html, propose_examples, title, ExampleProposal};
+
+    #[test]
+    fn converts_puzzle_description() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>The total distance is <code><em>11</em></code>, see <a href="/2024/day/1/input">your input</a>.</p>
+<ul>
+<li>A &amp; B</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<form method="post" action="1/answer"></form>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "The total distance is `*11*`, see [your input](/2024/day/1/input).",
+            "",
+            "- A & B",
+            "",
+            "Your puzzle answer was `1234`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(description_from_html(html), expected);
+    }
+
+    #[test]
+    fn handles_pages_without_articles() {
+        assert_eq!(description_from_html("<main></main>"), "\n");
+    }
+
+    #[test]
+    fn extracts_title() {
+        let description = "## --- Day 1: Historian Hysteria ---\n\ntext\n\n## --- Part Two ---\n";
+        assert_eq!(title(description), Some("Day 1: Historian Hysteria"));
+        assert_eq!(title("## --- Part Two ---"), None);
+    }
+
+    #[test]
+    fn proposes_shared_example() {
+        let description = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "Pairs are listed like this:",
+            "",
+            "```",
+            "3 4",
+            "```",
+            "",
+            "The distance is `*2*`, in total `*11*`.",
+            "",
+            "Your puzzle answer was `1234`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "The similarity score is *`31`*.",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            propose_examples(&description),
+            [ExampleProposal {
+                input: "3   4\n4   3\n".into(),
+                part_1: Some("11".into()),
+                part_2: Some("31".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn proposes_example_per_part() {
+        let description = [
+            "Take this example:",
+            "```",
+            "mul(2,4)",
+            "```",
+            "The result is `*8*`.",
+            "## --- Part Two ---",
+            "This time, consider a new example:",
+            "```",
+            "do()mul(2,4)",
+            "```",
+            "The result is `*4,6*`.",
+        ]
+        .join("\n");
+
+        let proposals = propose_examples(&description);
+        assert_eq!(proposals.len(), 2);
+        assert_eq!(proposals[0].part_1.as_deref(), Some("8"));
+        assert_eq!(proposals[0].part_2, None);
+        assert_eq!(proposals[1].input, "do()mul(2,4)\n");
+        assert_eq!(proposals[1].part_2.as_deref(), Some("4,6"));
+    }
+
+    #[test]
+    fn skips_examples_without_answers() {
+        assert!(propose_examples("For example:\n```\n1\n```\nNothing *emphasised*.").is_empty());
+        assert!(propose_examples("").is_empty());
+    }
+}
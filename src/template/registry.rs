@@ -0,0 +1,26 @@
+/// Table of solutions that can be called in-process.
+/// Entries are created by the `solution!` macro and registered by the `advent_of_code` binary.
+use std::sync::OnceLock;
+
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::Puzzle;
+
+/// A solution for a single puzzle, as generated by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    /// Runs every implemented part of the solution against `input`, printing the results.
+    pub run: fn(&str, &RunOptions) -> Vec<PartResult>,
+}
+
+static SOLUTIONS: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Registers the solutions available to this process. Only the first call has an effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Looks up the registered solution for `puzzle`, if any.
+pub fn get(puzzle: Puzzle) -> Option<&'static Solution> {
+    SOLUTIONS.get()?.iter().find(|s| s.puzzle == puzzle)
+}
@@ -1,41 +1,117 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process::ExitStatus,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use crate::template::runner::{
+    print_part_result, BenchOptions, InputSource, OutputFormat, PartResult, RunOptions, PARSE_PART,
+};
+use crate::template::{
+    inputs, registry, Calendar, Day, Puzzle, Solution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use super::timings::{Timing, Timings};
 
-    let mut need_space = false;
+/// Results of running the solution of a single day.
+pub struct DayRun {
+    pub day: Day,
+    /// Results of every part that ran, or `None` if the day is not solved or did not finish.
+    pub results: Option<Vec<PartResult>>,
+    pub status: DayStatus,
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// How running the solution of a single day ended.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DayStatus {
+    Ok,
+    /// The solution has not been scaffolded yet, or it has no input.
+    NotSolved,
+    /// The solution panicked, exited with an error, or one of its parts returned an error.
+    Failed(String),
+    /// The solution did not finish within the timeout, and was stopped.
+    Timeout(Duration),
+}
+
+impl DayStatus {
+    /// Status of a solution that ran to completion, based on the results of its parts.
+    fn from_results(results: &[PartResult]) -> Self {
+        let errors = results.iter().filter(|r| r.error.is_some()).count();
+        match (results.is_empty(), errors) {
+            (true, _) => DayStatus::NotSolved,
+            (false, 0) => DayStatus::Ok,
+            (false, errors) => DayStatus::Failed(format!("{errors} part(s) returned an error")),
+        }
+    }
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Ok => write!(f, "✔ ok"),
+            DayStatus::NotSolved => write!(f, "not solved"),
+            DayStatus::Failed(reason) => write!(f, "✖ failed ({reason})"),
+            DayStatus::Timeout(timeout) => write!(f, "⏱ timeout after {timeout:.1?}"),
+        }
+    }
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+/// Options that control how the solutions of multiple days are run.
+#[derive(Clone, Copy, Debug)]
+pub struct MultiRunOptions {
+    pub is_release: bool,
+    /// Bench the parts of every day. Days always run one after another in this case, so
+    /// measurements are not skewed by days running concurrently.
+    pub is_timed: bool,
+    pub bench: BenchOptions,
+    /// Stop a day that does not finish within this duration.
+    pub timeout: Option<Duration>,
+    /// Number of days that run concurrently. Concurrent days run in child processes, so their
+    /// output can be captured.
+    pub jobs: usize,
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+impl Default for MultiRunOptions {
+    fn default() -> Self {
+        MultiRunOptions {
+            is_release: false,
+            is_timed: false,
+            bench: BenchOptions::default(),
+            timeout: None,
+            jobs: 1,
+        }
+    }
+}
 
-    if is_timed {
-        let timings = Timings { data: timings };
+pub fn run_multi(
+    year: u16,
+    days_to_run: &HashSet<Day>,
+    options: &MultiRunOptions,
+) -> Option<Timings> {
+    let runs = run_days(year, days_to_run, options);
+
+    print_summary(&runs);
+
+    if options.is_timed {
+        let timings = Timings {
+            data: runs
+                .iter()
+                .filter_map(|run| {
+                    let results = run.results.as_ref()?;
+                    Some(timing_from_results(run.day, results, options.is_timed))
+                })
+                .collect(),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -46,10 +122,338 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Print the status of every day that ran, followed by the number of days per status.
+fn print_summary(runs: &[DayRun]) {
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+
+    let (mut ok, mut failed, mut timeout, mut not_solved) = (0, 0, 0, 0);
+
+    for run in runs {
+        println!("Day {}: {}", run.day, run.status);
+        match run.status {
+            DayStatus::Ok => ok += 1,
+            DayStatus::NotSolved => not_solved += 1,
+            DayStatus::Failed(_) => failed += 1,
+            DayStatus::Timeout(_) => timeout += 1,
+        }
+    }
+
+    println!("\n{ok} ok, {failed} failed, {timeout} timeout, {not_solved} not solved.");
+}
+
+/// Run the solutions of the given days of `year`, printing their output in day order.
+/// A day that panics, exits with an error or exceeds the timeout does not stop the remaining days.
+pub fn run_days(year: u16, days_to_run: &HashSet<Day>, options: &MultiRunOptions) -> Vec<DayRun> {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = Calendar::for_year(year)
+        .days()
+        .filter(|day| days_to_run.contains(day))
+        .collect();
+
+    let run_day = |day: Day, printer: &Printer| run_day(Puzzle::new(year, day), options, printer);
+
+    let jobs = if options.is_timed { 1 } else { options.jobs };
+
+    if jobs <= 1 {
+        return days
+            .iter()
+            .enumerate()
+            .map(|(i, &day)| {
+                print_day_header(day, i == 0);
+                let run = run_day(day, &Printer::Live);
+                print_day_status(&run.status);
+                run
+            })
+            .collect();
+    }
+
+    run_concurrently(&days, jobs, run_day)
+}
+
+thread_local! {
+    /// Printer of the day that runs on the current thread, while days run concurrently.
+    static DAY_PRINTER: RefCell<Option<Printer>> = const { RefCell::new(None) };
+}
+
+/// Run days on `jobs` threads. The output of every day is buffered, and printed once all days
+/// before it have been printed. This includes the messages of panics, which would otherwise be
+/// printed as soon as they happen.
+fn run_concurrently(
+    days: &[Day],
+    jobs: usize,
+    run_day: impl Fn(Day, &Printer) -> DayRun + Sync,
+) -> Vec<DayRun> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let previous_hook = Arc::new(panic::take_hook());
+    let hook = Arc::clone(&previous_hook);
+    panic::set_hook(Box::new(move |info| {
+        let printer = DAY_PRINTER.with(|printer| printer.borrow().clone());
+        match printer {
+            Some(printer) => printer.print(Line::Stderr(info.to_string())),
+            None => hook(info),
+        }
+    }));
+
+    let runs = thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (next, run_day, sender) = (&next, &run_day, sender.clone());
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+                let printer = Printer::buffered();
+                DAY_PRINTER.with(|current| current.replace(Some(printer.clone())));
+                let run = run_day(day, &printer);
+                DAY_PRINTER.with(|current| current.take());
+                if sender.send((index, run, printer)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut runs: Vec<DayRun> = Vec::with_capacity(days.len());
+        let mut finished = HashMap::new();
+
+        for (index, run, printer) in receiver {
+            finished.insert(index, (run, printer));
+            while let Some((run, printer)) = finished.remove(&runs.len()) {
+                print_day_header(run.day, runs.is_empty());
+                printer.flush();
+                print_day_status(&run.status);
+                runs.push(run);
+            }
+        }
+
+        runs
+    });
+
+    panic::set_hook(Box::new(move |info| previous_hook(info)));
+    runs
+}
+
+fn print_day_header(day: Day, is_first: bool) {
+    if !is_first {
+        println!();
+    }
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn print_day_status(status: &DayStatus) {
+    match status {
+        DayStatus::Ok => {}
+        DayStatus::NotSolved => println!("Not solved."),
+        status => println!("{status}"),
+    }
+}
+
+/// Run the solution of a single day, sending its output to `printer`.
+fn run_day(puzzle: Puzzle, options: &MultiRunOptions, printer: &Printer) -> DayRun {
+    // prefer calling registered solutions in-process, spawn the solution binary otherwise.
+    // threads can not be stopped, so a solution that may time out always runs in a child process.
+    // neither can their stdout be captured, so buffered days run in a child process, too.
+    let solution =
+        registry::get(puzzle).filter(|_| options.timeout.is_none() && !printer.is_buffered());
+    let (results, status) = match solution {
+        Some(solution) => run_in_process(solution, options, printer),
+        None => match child_commands::run_solution(puzzle, options, printer) {
+            Ok((results, status)) => ((!results.is_empty()).then_some(results), status),
+            Err(e) => (None, DayStatus::Failed(e.to_string())),
+        },
+    };
+
+    DayRun {
+        day: puzzle.day,
+        results,
+        status,
+    }
+}
+
+/// Destination of the output of a day.
+/// When days run concurrently, output is buffered so it can be printed in day order afterwards.
+#[derive(Clone)]
+pub enum Printer {
+    Live,
+    Buffered(Arc<Mutex<Vec<Line>>>),
+}
+
+/// A line of output of a solution.
+pub enum Line {
+    Result(PartResult),
+    Stdout(String),
+    Stderr(String),
+}
+
+impl Printer {
+    fn buffered() -> Self {
+        Printer::Buffered(Arc::default())
+    }
+
+    fn is_buffered(&self) -> bool {
+        matches!(self, Printer::Buffered(_))
+    }
+
+    pub fn print(&self, line: Line) {
+        match self {
+            Printer::Live => print_line(&line),
+            Printer::Buffered(lines) => lines.lock().unwrap().push(line),
+        }
+    }
+
+    /// Print all buffered lines.
+    fn flush(&self) {
+        if let Printer::Buffered(lines) = self {
+            lines
+                .lock()
+                .unwrap()
+                .drain(..)
+                .for_each(|line| print_line(&line));
+        }
+    }
+}
+
+fn print_line(line: &Line) {
+    match line {
+        Line::Result(result) => print_part_result(result),
+        Line::Stdout(line) => println!("{line}"),
+        Line::Stderr(line) => eprintln!("{line}"),
+    }
+}
+
+/// Run a registered solution against its input in the current process.
+fn run_in_process(
+    solution: &Solution,
+    options: &MultiRunOptions,
+    printer: &Printer,
+) -> (Option<Vec<PartResult>>, DayStatus) {
+    let input = match inputs::read(solution.puzzle) {
+        Ok(input) => input,
+        Err(inputs::Error::NotFound(path)) => {
+            printer.print(Line::Stderr(format!(
+                "could not open input file \"{}\"",
+                path.display()
+            )));
+            return (None, DayStatus::NotSolved);
+        }
+        Err(e) => return (None, DayStatus::Failed(e.to_string())),
+    };
+
+    let run_options = RunOptions {
+        is_timed: options.is_timed,
+        bench: options.bench,
+        ..RunOptions::default()
+    };
+
+    run_isolated(solution.run, input, run_options)
+}
+
+/// Run the solution of `puzzle` against the input file `path`, without printing its results.
+/// Registered solutions run in the current process, others in a child process.
+pub fn run_input(
+    puzzle: Puzzle,
+    path: &Path,
+) -> Result<(Option<Vec<PartResult>>, DayStatus), String> {
+    let input = fs::read_to_string(path)
+        .map_err(|e| format!("could not open input file \"{}\": {e}", path.display()))?;
+
+    let Some(solution) = registry::get(puzzle) else {
+        let input = InputSource::File(path.to_path_buf());
+        let options = MultiRunOptions::default();
+        let (results, status) =
+            child_commands::run_solution_with_input(puzzle, &input, &options, &Printer::buffered())
+                .map_err(|e| e.to_string())?;
+        return Ok(((!results.is_empty()).then_some(results), status));
+    };
+
+    let options = RunOptions {
+        format: OutputFormat::Silent,
+        ..RunOptions::default()
+    };
+    Ok(run_isolated(solution.run, input, options))
+}
+
+/// Run a solution, catching panics so they do not abort the remaining days.
+/// Solutions that run in-process can not be stopped, days with a timeout run in a child process.
+fn run_isolated(
+    run: fn(&str, &RunOptions) -> Vec<PartResult>,
+    input: String,
+    options: RunOptions,
+) -> (Option<Vec<PartResult>>, DayStatus) {
+    match panic::catch_unwind(AssertUnwindSafe(|| run(&input, &options))) {
+        Ok(results) => {
+            let status = DayStatus::from_results(&results);
+            (Some(results), status)
+        }
+        Err(payload) => (None, DayStatus::Failed(panic_message(payload.as_ref()))),
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+
+    match message {
+        Some(message) => format!("panicked: {message}"),
+        None => "panicked".into(),
+    }
+}
+
+fn timing_from_results(day: Day, results: &[PartResult], is_timed: bool) -> Timing {
+    let mut timing = Timing {
+        day,
+        ..Default::default()
+    };
+
+    // only benched parts with an answer, and the parse stage, count towards timings.
+    if !is_timed {
+        return timing;
+    }
+
+    for result in results
+        .iter()
+        .filter(|r| r.answer.is_some() || (r.part == PARSE_PART && r.error.is_none()))
+    {
+        let duration = format!("{:.1?}", result.measurement.duration);
+        match result.part {
+            PARSE_PART => {
+                timing.parse = Some(duration);
+                timing.parse_stats = Some(result.measurement);
+            }
+            1 => {
+                timing.part_1 = Some(duration);
+                timing.part_1_stats = Some(result.measurement);
+            }
+            2 => {
+                timing.part_2 = Some(duration);
+                timing.part_2_stats = Some(result.measurement);
+            }
+            _ => continue,
+        }
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timing.total_nanos += result.measurement.duration.as_nanos() as f64;
+        }
+    }
+
+    timing
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// The solution binary could not be built.
+    Build(ExitStatus),
+    /// The build succeeded, but did not report the path of the solution binary.
+    MissingExecutable,
     IO(io::Error),
 }
 
@@ -59,48 +463,84 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution."),
+            Error::Build(status) => write!(f, "build failed with {status}"),
+            Error::MissingExecutable => write!(f, "could not find the solution binary"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing their structured output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayStatus, Error, Line, MultiRunOptions, Printer};
+    use crate::template::runner::{InputSource, PartResult};
+    use crate::template::{inputs, paths, Puzzle};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{self, BufRead, BufReader},
+        path::PathBuf,
+        process::{Child, Command, ExitStatus, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant},
     };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    use tinyjson::JsonValue;
+
+    /// Run the solution bin for a given puzzle.
+    /// The solution is stopped if it does not finish within `timeout`, results of the parts that
+    /// finished before are kept.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        options: &MultiRunOptions,
+        printer: &Printer,
+    ) -> Result<(Vec<PartResult>, DayStatus), Error> {
+        // skip command invocation for days that have not been scaffolded yet, or have no input.
+        if !paths::bin_path(puzzle).exists() || !inputs::exists(puzzle) {
+            return Ok((vec![], DayStatus::NotSolved));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+        run_solution_with_input(puzzle, &InputSource::Input, options, printer)
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    /// Run the solution bin for a given puzzle against `input`.
+    pub fn run_solution_with_input(
+        puzzle: Puzzle,
+        input: &InputSource,
+        options: &MultiRunOptions,
+        printer: &Printer,
+    ) -> Result<(Vec<PartResult>, DayStatus), Error> {
+        // build the binary upfront, so the timeout only applies to running the solution.
+        let executable = build_solution(puzzle, options.is_release)?;
+
+        // request line-delimited JSON so results can be read back without parsing decorated text.
+        let mut args: Vec<String> = vec!["--format".into(), "jsonl".into()];
+        args.extend(input.to_args());
+
+        if options.is_timed {
+            // mirror `--time` flag and bench options to child invocations.
+            args.push("--time".into());
+            args.extend(options.bench.to_args());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let mut command = Command::new(executable);
+        command.args(&args);
+        run_command(&mut command, options.timeout, printer)
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+    /// Run a solution binary, forwarding its output to `printer` while grabbing part results.
+    /// The command is killed if it does not finish within `timeout`, so it can not print or use
+    /// the CPU afterwards.
+    pub fn run_command(
+        command: &mut Command,
+        timeout: Option<Duration>,
+        printer: &Printer,
+    ) -> Result<(Vec<PartResult>, DayStatus), Error> {
+        let mut cmd = command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,150 +548,313 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let stderr_printer = printer.clone();
+        let stderr_thread = thread::spawn(move || {
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                stderr_printer.print(Line::Stderr(line));
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stdout_printer = printer.clone();
+        let stdout_thread = thread::spawn(move || {
+            let mut results = vec![];
+            for line in stdout.lines().map_while(Result::ok) {
+                match parse_part_result(&line) {
+                    Some(result) => {
+                        stdout_printer.print(Line::Result(result.clone()));
+                        results.push(result);
+                    }
+                    // lines that are not results, e.g. debug output of a solution, are forwarded as-is.
+                    None => stdout_printer.print(Line::Stdout(line)),
+                }
+            }
+            results
+        });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let exit_status = wait_with_timeout(&mut cmd, timeout)?;
 
-        Ok(output)
-    }
+        let results = stdout_thread.join().map_err(|_| Error::BrokenPipe)?;
+        stderr_thread.join().map_err(|_| Error::BrokenPipe)?;
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+        let status = match (exit_status, timeout) {
+            (Some(exit_status), _) if !exit_status.success() => {
+                DayStatus::Failed(format!("exited with {exit_status}"))
+            }
+            (Some(_), _) => DayStatus::from_results(&results),
+            (None, timeout) => DayStatus::Timeout(timeout.unwrap_or_default()),
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        Ok((results, status))
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    /// Build the solution bin for a given puzzle, returns the path of the executable.
+    fn build_solution(puzzle: Puzzle, is_release: bool) -> Result<PathBuf, Error> {
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--message-format=json-render-diagnostics".into(),
+            "--bin".into(),
+            puzzle.to_string(),
+        ];
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        if is_release {
+            args.push("--release".into());
+        }
 
-                timings.total_nanos += nanos;
-            });
+        // compiler diagnostics are rendered to stderr, stdout only holds JSON messages.
+        let output = Command::new("cargo")
+            .args(&args)
+            .current_dir(paths::root())
+            .stderr(Stdio::inherit())
+            .output()?;
 
-        timings
-    }
+        if !output.status.success() {
+            return Err(Error::Build(output.status));
+        }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(parse_executable)
+            .ok_or(Error::MissingExecutable)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+    /// Parse the path of an executable from a `compiler-artifact` message of `cargo build`.
+    pub fn parse_executable(line: &str) -> Option<PathBuf> {
+        let json = JsonValue::from_str(line).ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        if message.get("reason")?.get::<String>()? != "compiler-artifact" {
+            return None;
+        }
 
-        Some((str_timing, parsed_timing))
+        message
+            .get("executable")?
+            .get::<String>()
+            .map(PathBuf::from)
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Wait for a child to exit. If it does not exit within `timeout`, it is killed and `None` is
+    /// returned.
+    fn wait_with_timeout(
+        cmd: &mut Child,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, io::Error> {
+        let Some(timeout) = timeout else {
+            return cmd.wait().map(Some);
+        };
+
+        let start = Instant::now();
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(Some(status));
+            }
+            if start.elapsed() >= timeout {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(None);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Parse a line of `--format jsonl` output into a part result.
+    pub fn parse_part_result(line: &str) -> Option<PartResult> {
+        let json = JsonValue::from_str(line).ok()?;
+        PartResult::try_from(&json).ok()
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_executable, parse_part_result, wait_with_timeout};
+        use std::{path::PathBuf, process::Command, time::Duration};
 
         use crate::day;
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        fn parses_part_results() {
+            let res = parse_part_result(
+                r#"{"day":1,"part":2,"answer":"10","duration_ns":74130,"samples":100,"min":70000,"max":80000}"#,
+            )
+            .unwrap();
+            assert_eq!(res.day, day!(1));
+            assert_eq!(res.part, 2);
+            assert_eq!(res.answer, Some("10".into()));
+            assert_eq!(res.measurement.duration, Duration::from_nanos(74130));
+            assert_eq!(res.measurement.samples, 100);
+            assert_eq!(res.measurement.min, Duration::from_nanos(70000));
+            assert_eq!(res.measurement.max, Duration::from_nanos(80000));
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn parses_missing_answers() {
+            let res = parse_part_result(
+                r#"{"day":1,"part":1,"answer":null,"duration_ns":0,"samples":1,"min":0,"max":0}"#,
+            )
+            .unwrap();
+            assert_eq!(res.day, day!(1));
+            assert_eq!(res.answer, None);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn parses_executables() {
+            let line = r#"{"reason":"compiler-artifact","target":{"name":"2024-01"},"executable":"/repo/target/debug/2024-01","fresh":true}"#;
+            assert_eq!(
+                parse_executable(line),
+                Some(PathBuf::from("/repo/target/debug/2024-01"))
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(parse_executable(
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code"},"executable":null}"#
+            )
+            .is_none());
+            assert!(parse_executable(r#"{"reason":"build-finished","success":true}"#).is_none());
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn kills_children_after_timeout() {
+            let timeout = Some(Duration::from_millis(50));
+
+            let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+            assert!(wait_with_timeout(&mut child, timeout).unwrap().is_none());
+
+            let mut child = Command::new("true").spawn().unwrap();
+            let status = wait_with_timeout(&mut child, timeout).unwrap();
+            assert!(status.is_some_and(|s| s.success()));
+        }
+
+        #[test]
+        fn ignores_other_output() {
+            assert!(parse_part_result("Part 1: 0 (74.13ns @ 100000 samples)").is_none());
+            assert!(parse_part_result("[1, 2, 3]").is_none());
+            assert!(parse_part_result(r#"{"day":"01"}"#).is_none());
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::child_commands::run_command;
+    use super::{run_concurrently, run_isolated, DayRun, DayStatus, Line, Printer};
+    use crate::{
+        day,
+        template::runner::{Measurement, PartResult, RunOptions},
+    };
+    use std::{process::Command, thread, time::Duration};
+
+    fn result(part: u8, error: Option<&str>) -> PartResult {
+        PartResult {
+            day: day!(1),
+            part,
+            answer: error.is_none().then(|| "42".into()),
+            error: error.map(Into::into),
+            measurement: Measurement::default(),
         }
     }
+
+    fn solve(input: &str, _: &RunOptions) -> Vec<PartResult> {
+        match input {
+            "panic" => panic!("bad input"),
+            _ => vec![result(1, None), result(2, None)],
+        }
+    }
+
+    fn run(input: &str) -> (Option<Vec<PartResult>>, DayStatus) {
+        run_isolated(solve, input.into(), RunOptions::default())
+    }
+
+    #[test]
+    fn runs_solutions() {
+        let (results, status) = run("1 2 3");
+        assert_eq!(results.map(|r| r.len()), Some(2));
+        assert_eq!(status, DayStatus::Ok);
+    }
+
+    #[test]
+    fn isolates_panics() {
+        let (results, status) = run("panic");
+        assert!(results.is_none());
+        assert_eq!(status, DayStatus::Failed("panicked: bad input".into()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stops_days_after_timeout() {
+        let printer = Printer::buffered();
+        let timeout = Duration::from_millis(200);
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            "echo started; while true; do echo running; sleep 0.01; done",
+        ]);
+
+        let (results, status) = run_command(&mut command, Some(timeout), &printer).unwrap();
+        assert!(results.is_empty());
+        assert_eq!(status, DayStatus::Timeout(timeout));
+
+        let lines = || match &printer {
+            Printer::Buffered(lines) => lines
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|line| matches!(line, Line::Stdout(_)))
+                .count(),
+            Printer::Live => unreachable!(),
+        };
+
+        // the day was killed, it does not print anything after its status was reported.
+        let printed = lines();
+        assert!(printed > 0);
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(lines(), printed);
+    }
+
+    #[test]
+    fn runs_days_concurrently_in_order() {
+        let days = [day!(1), day!(2), day!(3), day!(4)];
+
+        // later days finish first, their output is still returned in day order.
+        let runs = run_concurrently(&days, 3, |day, printer: &Printer| {
+            thread::sleep(Duration::from_millis(u64::from(5 - day.into_inner()) * 10));
+            printer.print(Line::Stdout(format!("day {day}")));
+            DayRun {
+                day,
+                results: None,
+                status: DayStatus::Ok,
+            }
+        });
+
+        assert_eq!(runs.iter().map(|r| r.day).collect::<Vec<_>>(), days);
+    }
+
+    #[test]
+    fn buffers_panic_messages() {
+        let days = [day!(1), day!(2)];
+
+        run_concurrently(&days, 2, |day, printer: &Printer| {
+            let (_, status) = run("panic");
+            let Printer::Buffered(lines) = printer else {
+                unreachable!()
+            };
+            let buffered = lines
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|line| matches!(line, Line::Stderr(line) if line.contains("bad input")));
+            assert!(buffered);
+            DayRun {
+                day,
+                results: None,
+                status,
+            }
+        });
+    }
+
+    #[test]
+    fn derives_status_from_results() {
+        assert_eq!(DayStatus::from_results(&[]), DayStatus::NotSolved);
+        assert_eq!(
+            DayStatus::from_results(&[result(1, None), result(2, Some("invalid input"))]),
+            DayStatus::Failed("1 part(s) returned an error".into())
+        );
+    }
 }
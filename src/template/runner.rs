@@ -1,39 +1,686 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
+use std::error::Error;
 use std::fmt::Display;
+use std::fs;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process};
+use tinyjson::JsonValue;
 
+use crate::template::answers::Answers;
+use crate::template::examples::Manifest;
+use crate::template::submissions::{Outcome, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, config, inputs, paths, Day, Puzzle, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Format in which a solution binary reports its results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output, decorated with ANSI codes.
+    #[default]
+    Text,
+    /// A single JSON array containing every part, printed once all parts ran.
+    Json,
+    /// One JSON object per line, printed as soon as a part finishes.
+    JsonLines,
+    /// Nothing is printed, results are only returned. Used when the results of a solution are
+    /// checked instead of printed, e.g. when running it against several inputs.
+    Silent,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" => Ok(OutputFormat::JsonLines),
+            _ => Err(format!(
+                "unknown output format `{s}`, expecting one of: text, json, jsonl"
+            )),
+        }
+    }
+}
+
+/// Options that control how long a part is benched.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchOptions {
+    /// Approximate time budget for the measured iterations of a part.
+    pub time: Duration,
+    /// Minimum number of measured iterations, regardless of the time budget.
+    pub min_samples: u128,
+    /// Maximum number of measured iterations, unless `min_samples` is higher.
+    pub max_samples: u128,
+}
+
+/// The bench options configured in `aoc.toml`.
+impl Default for BenchOptions {
+    fn default() -> Self {
+        config::get().bench
+    }
+}
+
+impl BenchOptions {
+    pub const DEFAULT: BenchOptions = BenchOptions {
+        time: Duration::from_secs(1),
+        min_samples: 10,
+        max_samples: 10000,
+    };
+
+    /// Parse `--bench-time <seconds>` and `--min-samples <n>`, falling back to defaults.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let defaults = BenchOptions::default();
+
+        let time = args
+            .opt_value_from_fn("--bench-time", |s| {
+                s.parse::<f64>()
+                    .map_err(|e| e.to_string())
+                    .and_then(|x| Duration::try_from_secs_f64(x).map_err(|e| e.to_string()))
+            })?
+            .unwrap_or(defaults.time);
+
+        let min_samples = args
+            .opt_value_from_str("--min-samples")?
+            .unwrap_or(defaults.min_samples)
+            .max(1);
+
+        Ok(BenchOptions {
+            time,
+            min_samples,
+            ..defaults
+        })
+    }
+
+    /// Arguments that pass these options on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--bench-time".into(),
+            self.time.as_secs_f64().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+        ]
+    }
+
+    /// Number of measured iterations for a part whose first run took `base_time`.
+    fn iterations(&self, base_time: &Duration) -> u128 {
+        let max_samples = cmp::max(self.max_samples, self.min_samples);
+        (self.time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, max_samples)
+    }
+}
+
+/// Input a solution binary runs against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/{year}/inputs`.
+    #[default]
+    Input,
+    /// The example in `data/{year}/examples`, or one of its variants, e.g. `01-2.txt`.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse `--input <path>`, `--input -` (stdin) and `--example [<n>]`.
+    /// Any argument following `--example` that is not an option is taken as the number of the
+    /// example, so `--example 16` runs example 16 and the day has to come first, e.g.
+    /// `cargo solve 16 --example`.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let example = match args.opt_value_from_str::<_, u8>("--example") {
+            Ok(n) => n.map(Some),
+            // `--example` is the last argument, or followed by another option.
+            Err(pico_args::Error::OptionWithoutAValue(_)) => {
+                args.contains("--example").then_some(None)
+            }
+            Err(pico_args::Error::Utf8ArgumentParsingFailed { value, .. })
+                if value.starts_with('-') =>
+            {
+                args.contains("--example").then_some(None)
+            }
+            Err(e) => return Err(e),
+        };
+        let path: Option<PathBuf> = args.opt_value_from_str("--input")?;
+
+        match (example, path) {
+            (Some(_), Some(_)) => Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "`--input` and `--example` can not be combined.".into(),
+            }),
+            (Some(n), None) => Ok(InputSource::Example(n)),
+            (None, Some(path)) if path.as_os_str() == "-" => Ok(InputSource::Stdin),
+            (None, Some(path)) => Ok(InputSource::File(path)),
+            (None, None) => Ok(InputSource::Input),
+        }
+    }
+
+    /// Arguments that pass this input on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Input => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// The parameters of the example in the manifest of `puzzle` that uses this example file, if any.
+    pub fn example_params(&self, puzzle: Puzzle) -> Result<Vec<(String, String)>, String> {
+        let file = match self {
+            InputSource::Example(None) => format!("{}.txt", puzzle.day),
+            InputSource::Example(Some(n)) => format!("{}-{n}.txt", puzzle.day),
+            _ => return Ok(vec![]),
+        };
+        let manifest = Manifest::read(puzzle)?;
+        Ok(manifest
+            .examples
+            .iter()
+            .find(|example| example.file.as_os_str() == file.as_str())
+            .map(|example| example.params.clone())
+            .unwrap_or_default())
+    }
+
+    /// Read the input for `puzzle`.
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, String> {
+        let path = match self {
+            InputSource::Input => return inputs::read(puzzle).map_err(|e| e.to_string()),
+            InputSource::Example(None) => paths::data_path("examples", puzzle),
+            InputSource::Example(Some(n)) => paths::data_part_path("examples", puzzle, *n),
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => {
+                return io::read_to_string(io::stdin())
+                    .map_err(|e| format!("could not read input from stdin: {e}"));
+            }
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| format!("could not open input file \"{}\": {e}", path.display()))
+    }
+}
+
+/// Options that control how solution parts are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once.
+    pub is_timed: bool,
+    pub bench: BenchOptions,
+    /// Submit the answer of this part after running it.
+    pub submit: Option<u8>,
+    /// Record the submitted answer in `data/answers.json` if it was accepted.
+    pub record: bool,
+    /// Submit even if the submission history suggests the answer is wrong.
+    pub force: bool,
+    pub format: OutputFormat,
+    /// Input of a solution binary. Solutions that run in-process always use the puzzle input.
+    pub input: InputSource,
+    /// Only run this part, e.g. when checking it against an example. A shared parse stage still runs.
+    pub part: Option<u8>,
+    /// Overrides of the [parameters](Params) of the solution, in order, e.g. `("width", "11")`.
+    pub params: Vec<(String, String)>,
+}
+
+impl RunOptions {
+    /// Parse the options passed to the solution binary of `puzzle`, e.g. `cargo run --bin 01 -- --time`.
+    pub fn from_args(puzzle: Puzzle) -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let Ok(submit) = args.opt_value_from_str("--submit") else {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+            process::exit(1);
+        };
+
+        let format = match args.opt_value_from_str("--format") {
+            Ok(format) => format.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let bench = match BenchOptions::from_args(&mut args) {
+            Ok(bench) => bench,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let input = match InputSource::from_args(&mut args) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let overrides: Vec<(String, String)> = match args.values_from_fn("--param", parse_param) {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        // answers are only accepted for the personal puzzle input, with the default parameters.
+        if submit.is_some() && (input != InputSource::Input || !overrides.is_empty()) {
+            eprintln!(
+                "`--submit` can only be used with the puzzle input and its default parameters."
+            );
+            process::exit(1);
+        }
+
+        // examples run with the parameters of their manifest entry, `--param` overrides them.
+        let mut params = match input.example_params(puzzle) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+        params.extend(overrides);
+
+        RunOptions {
+            is_timed: args.contains("--time"),
+            bench,
+            submit,
+            record: args.contains("--record") || config::get().submit.record,
+            force: args.contains("--force"),
+            format,
+            input,
+            part: None,
+            params,
+        }
+    }
+
+    /// Whether `part` should run, see [`RunOptions::part`].
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|only| only == part)
+    }
+}
+
+/// Timing information collected while running a solution part.
+/// Statistics are computed over the samples that remain after outlier rejection.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Measurement {
+    /// Mean duration.
+    pub duration: Duration,
+    /// Number of measured iterations, including rejected outliers.
+    pub samples: u128,
+    pub min: Duration,
+    pub median: Duration,
+    /// 95th percentile.
+    pub p95: Duration,
+    pub max: Duration,
+    /// Standard deviation.
+    pub stddev: Duration,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR) that were rejected.
+    pub outliers: u128,
+}
+
+impl Measurement {
+    /// A measurement made up of a single run.
+    pub fn single(duration: Duration) -> Self {
+        Measurement {
+            duration,
+            samples: 1,
+            min: duration,
+            median: duration,
+            p95: duration,
+            max: duration,
+            stddev: Duration::ZERO,
+            outliers: 0,
+        }
+    }
+
+    /// Compute statistics over a set of samples, rejecting outliers.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Measurement::default();
+        }
+
+        samples.sort_unstable();
+        let count = samples.len() as u128;
+
+        let q1 = percentile(&samples, 25.0).as_secs_f64();
+        let q3 = percentile(&samples, 75.0).as_secs_f64();
+        let fence = 1.5 * (q3 - q1);
+
+        let kept: Vec<Duration> = samples
+            .iter()
+            .copied()
+            .filter(|x| (q1 - fence..=q3 + fence).contains(&x.as_secs_f64()))
+            .collect();
+        // NOTE: the median always lies within the fences, so `kept` is never empty.
+
+        let mean = average_duration(&kept);
+        #[allow(clippy::cast_precision_loss)]
+        let variance = kept
+            .iter()
+            .map(|x| (x.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / kept.len() as f64;
+
+        Measurement {
+            duration: mean,
+            samples: count,
+            min: kept[0],
+            median: percentile(&kept, 50.0),
+            p95: percentile(&kept, 95.0),
+            max: kept[kept.len() - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: count - kept.len() as u128,
+        }
+    }
+}
+
+/// Part number under which the timing of a shared parse stage is reported.
+pub const PARSE_PART: u8 = 0;
+
+/// The outcome of running a single solution part, or of the parse stage if `part` is [`PARSE_PART`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The error the part failed with, including its chain of sources.
+    pub error: Option<String>,
+    pub measurement: Measurement,
+}
+
+/// Value returned by a solution part: an `Option` for parts that might not be implemented yet,
+/// or a `Result` for parts that can fail.
+pub trait PartOutput {
+    /// Converts the output to the answer of the part, `Ok(None)` if the part is not implemented.
+    fn into_answer(self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, String> {
+        Ok(self.map(|answer| answer.to_string()))
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<T, E> {
+    fn into_answer(self) -> Result<Option<String>, String> {
+        self.map(|answer| Some(answer.to_string()))
+            .map_err(|e| format_error(&*e.into()))
+    }
+}
+
+/// Formats an error followed by the chain of its sources, e.g. `invalid prize: invalid digit`.
+pub fn format_error(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(e) = source {
+        message.push_str(&format!(": {e}"));
+        source = e.source();
+    }
+    message
+}
+
+/// Parameters of a solution that differ between its examples and the real input, e.g. the size of
+/// a grid. Declared with [`params!`](crate::params), defaults apply to the real input.
+pub trait Params: Default {
+    /// Override the parameter `key` with `value`.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// Solutions without parameters.
+impl Params for () {
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(format!(
+            "unknown parameter `{key}`, the solution has no parameters."
+        ))
+    }
+}
+
+/// Parse a `key=value` parameter override, e.g. `--param width=11`.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().into(), value.trim().into()))
+        }
+        _ => Err(format!("invalid parameter `{s}`, expecting `key=value`.")),
+    }
+}
+
+/// Resolve the parameters of a solution from their defaults and the overrides in `options`.
+/// Invalid overrides are reported, the parts should not run in that case and the solution returns
+/// the results in the error instead, i.e. a failed parse stage.
+pub fn run_params<P: Params>(puzzle: Puzzle, options: &RunOptions) -> Result<P, Vec<PartResult>> {
+    let mut params = P::default();
+
+    for (key, value) in &options.params {
+        if let Err(e) = params.set(key, value) {
+            let result = PartResult {
+                day: puzzle.day,
+                part: PARSE_PART,
+                answer: None,
+                error: Some(e),
+                measurement: Measurement::default(),
+            };
+            report_result(&result, options);
+            return Err(vec![result]);
+        }
+    }
+
+    Ok(params)
+}
+
+/// Marks stages that only take their input, see [`Stage`].
+pub struct WithoutParams;
+
+/// Marks stages that take their input and the parameters of the solution, see [`Stage`].
+pub struct WithParams;
+
+/// A stage of a solution, e.g. a part. Stages are functions of their input, and optionally of a
+/// reference to the [parameters](Params) of the solution.
+pub trait Stage<I, P, T, Marker> {
+    fn call(&self, input: I, params: &P) -> T;
+}
+
+impl<F: Fn(I) -> T, I, P, T> Stage<I, P, T, WithoutParams> for F {
+    fn call(&self, input: I, _params: &P) -> T {
+        self(input)
+    }
+}
+
+impl<F: Fn(I, &P) -> T, I, P, T> Stage<I, P, T, WithParams> for F {
+    fn call(&self, input: I, params: &P) -> T {
+        self(input, params)
+    }
+}
+
+/// Run a single part of a solution. `input` is copied into every bench iteration, so it is either
+/// the raw input or a reference to an input that was prepared once upfront.
+pub fn run_part<I: Copy, P, T: PartOutput, M>(
+    func: impl Stage<I, P, T, M>,
+    input: I,
+    params: &P,
+    puzzle: Puzzle,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
+    let is_text = options.format == OutputFormat::Text;
+
+    let bench = options.is_timed.then_some(&options.bench);
+
+    let func = |input: I| func.call(input, params);
+    let (answer, measurement) = run_timed(func, input, bench, is_text, |output| {
+        let answer = output.into_answer();
+        if is_text {
+            print_result(answer.as_ref(), &part_str, "");
+        }
+        answer
+    });
+
+    let (answer, error) = match answer {
+        Ok(answer) => (answer, None),
+        Err(error) => (None, Some(error)),
+    };
+
+    let result = PartResult {
+        day: puzzle.day,
+        part,
+        answer,
+        error,
+        measurement,
+    };
+
+    report_result(&result, options);
+
+    if let Some(answer) = &result.answer {
+        if options.submit == Some(part) {
+            // read the recorded answers first, an invalid file must not be replaced after submitting.
+            match options
+                .record
+                .then(|| Answers::read_from_file(puzzle.year))
+                .transpose()
+            {
+                Ok(answers) => {
+                    let outcome = submit_result(answer, puzzle, part, options.force);
+                    if let (Some(answers), Some(Outcome::Correct)) = (answers, outcome) {
+                        record_result(answers, answer, puzzle, part);
+                    }
+                }
+                Err(e) => eprintln!(
+                    "✖ Not submitting {answer:?}: could not read the recorded answers: {e}"
+                ),
+            }
+        }
+    }
+
+    result
+}
+
+/// Run the shared parse stage of a solution, returning its output along with its timing.
+pub fn run_parse<'a, P, T, M>(
+    func: impl Stage<&'a str, P, T, M>,
+    input: &'a str,
+    params: &P,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> (T, PartResult) {
+    let (parsed, result) = time_parse(func, input, params, puzzle, options);
+    report_result(&result, options);
+    (parsed, result)
+}
+
+/// Run the fallible prepare stage of a solution, returning the prepared input along with its timing.
+/// Errors are reported with the result of the stage, along with the chain of their sources. The
+/// prepared input is `None` in that case, and the parts of the solution should not run.
+pub fn run_prepare<'a, P, T, E: Into<Box<dyn Error>>, M>(
+    func: impl Stage<&'a str, P, Result<T, E>, M>,
+    input: &'a str,
+    params: &P,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> (Option<T>, PartResult) {
+    let (prepared, mut result) = time_parse(func, input, params, puzzle, options);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let prepared = match prepared {
+        Ok(prepared) => Some(prepared),
+        Err(e) => {
+            result.error = Some(format_error(&*e.into()));
+            None
+        }
+    };
+
+    report_result(&result, options);
+    (prepared, result)
+}
+
+/// Run a parse or prepare stage without reporting its result.
+fn time_parse<'a, P, T, M>(
+    func: impl Stage<&'a str, P, T, M>,
+    input: &'a str,
+    params: &P,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> (T, PartResult) {
+    let is_text = options.format == OutputFormat::Text;
+    let bench = options.is_timed.then_some(&options.bench);
+
+    let func = |input: &'a str| func.call(input, params);
+
+    let (parsed, measurement) = run_timed(func, input, bench, is_text, |parsed| {
+        if is_text {
+            print!("Parse:");
+        }
+        parsed
+    });
+
+    let result = PartResult {
+        day: puzzle.day,
+        part: PARSE_PART,
+        answer: None,
+        error: None,
+        measurement,
+    };
+
+    (parsed, result)
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Print the result of a part as soon as it finished, if the output format requires it.
+fn report_result(result: &PartResult, options: &RunOptions) {
+    match options.format {
+        OutputFormat::Text => print_part_result(result),
+        OutputFormat::JsonLines => println!("{}", JsonValue::from(result).stringify().unwrap()),
+        OutputFormat::Json | OutputFormat::Silent => {}
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Print the results of all parts of a day once they ran, if the output format requires it.
+pub fn print_results(results: &[PartResult], options: &RunOptions) {
+    if options.format == OutputFormat::Json {
+        let json = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+        println!("{}", json.stringify().unwrap());
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+/// Print the final, human-readable line for a part that has finished running.
+pub(crate) fn print_part_result(result: &PartResult) {
+    if result.part == PARSE_PART {
+        match &result.error {
+            Some(error) => print_result(Err(error), "Parse", &format_duration(&result.measurement)),
+            None => {
+                print!("\r");
+                println!("Parse:{}", format_duration(&result.measurement));
+            }
+        }
+        return;
+    }
+
+    let answer = match &result.error {
+        Some(error) => Err(error),
+        None => Ok(&result.answer),
+    };
+
+    print_result(
+        answer,
+        &format!("Part {}", result.part),
+        &format_duration(&result.measurement),
+    );
+}
+
+/// Run a solution part. The behavior differs depending on whether bench options are passed:
+///  1. if not, the function is executed once.
+///  2. if they are, the function is benched (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+fn run_timed<I: Copy, T, R>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    bench_options: Option<&BenchOptions>,
+    show_progress: bool,
+    hook: impl FnOnce(T) -> R,
+) -> (R, Measurement) {
     let timer = Instant::now();
     let result = {
-        let input = input.clone();
-
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
@@ -41,64 +688,90 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let measurement = match bench_options {
+        Some(options) => bench(func, input, &base_time, options, show_progress),
+        None => Measurement::single(base_time),
     };
 
-    (result, run.0, run.1)
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &BenchOptions,
+    show_progress: bool,
+) -> Measurement {
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let bench_iterations = options.iterations(base_time);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // warm up caches and branch predictors before measuring.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
         let timer = Instant::now();
-        black_box(func(black_box(cloned)));
+        black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+    Measurement::from_samples(timers)
+}
+
+fn average_duration(numbers: &[Duration]) -> Duration {
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(
+        (numbers
+            .iter()
+            .map(std::time::Duration::as_nanos)
+            .sum::<u128>()
+            / numbers.len() as u128) as u64,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+fn format_duration(measurement: &Measurement) -> String {
+    let Measurement {
+        duration,
+        samples,
+        median,
+        p95,
+        stddev,
+        ..
+    } = measurement;
+
+    if *samples <= 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} ± {stddev:.1?} @ {samples} samples, median {median:.1?}, p95 {p95:.1?})")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: Result<&Option<String>, &String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +791,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,44 +799,471 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(error) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ error");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ error: {error}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Record an answer in `data/{year}/answers.json`.
+fn record_result(mut answers: Answers, answer: &str, puzzle: Puzzle, part: u8) {
+    answers.set(puzzle.day, part, answer);
+    match answers.store_file(puzzle.year) {
+        Ok(()) => println!("Recorded answer for {puzzle}, part {part}."),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+/// Submit one part of the solution, printing the response of the server.
+/// Answers the submission history rules out are not submitted, unless `force` is set or the
+/// guard is disabled in `aoc.toml`. Empty, `0` and multi-line answers always need `force`.
+fn submit_result(result: &str, puzzle: Puzzle, part: u8, force: bool) -> Option<Outcome> {
+    let mut history = match Submissions::read_from_file(puzzle.year) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("✖ Not submitting {result:?}: could not read the submission history: {e}");
+            return None;
+        }
+    };
+
+    // malformed answers are rejected even if the guard is disabled.
+    let check = if config::get().submit.guard {
+        history.check(puzzle.day, part, result)
+    } else {
+        Submissions::check_answer(result)
+    };
+
+    if let Err(rejection) = check {
+        if !force {
+            eprintln!("✖ Not submitting {result:?}: {rejection} Pass --force to submit anyway.");
+            return None;
+        }
+        eprintln!("Submitting {result:?} despite: {rejection}");
+    }
+
+    println!("Submitting result...");
+    let outcome = match aoc_client::submit(puzzle, part, result) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("✖ Submission failed: {e}");
+            return None;
+        }
+    };
+
+    match outcome {
+        Outcome::Correct => println!("✔ {outcome}"),
+        _ => eprintln!("✖ {outcome}"),
+    }
+
+    history.push(puzzle.day, part, result, outcome);
+    if let Err(e) = history.store_file(puzzle.year) {
+        eprintln!("Failed to store submission history: {e}");
+    }
+
+    Some(outcome)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Measurement> for JsonValue {
+    fn from(value: &Measurement) -> Self {
+        JsonValue::Object(value.json_fields())
+    }
+}
+
+impl Measurement {
+    #[allow(clippy::cast_precision_loss)]
+    fn json_fields(&self) -> HashMap<String, JsonValue> {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+        map.insert("duration_ns".into(), nanos(self.duration));
+        map.insert("samples".into(), JsonValue::Number(self.samples as f64));
+        map.insert("min".into(), nanos(self.min));
+        map.insert("median".into(), nanos(self.median));
+        map.insert("p95".into(), nanos(self.p95));
+        map.insert("max".into(), nanos(self.max));
+        map.insert("stddev".into(), nanos(self.stddev));
+        map.insert("outliers".into(), JsonValue::Number(self.outliers as f64));
+
+        map
+    }
+}
+
+impl TryFrom<&JsonValue> for Measurement {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected measurement to be a JSON object.")?;
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected measurement.{key} to be a number."))
+        };
+        let nanos = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        let duration = nanos("duration_ns")?;
+
+        // NOTE: statistics other than the mean are optional, results written before they were
+        // introduced only contain `duration_ns`, `samples`, `min` and `max`.
+        let nanos_or = |key: &str, default: Duration| nanos(key).or(Ok::<_, String>(default));
+
+        Ok(Measurement {
+            duration,
+            samples: number("samples")? as u128,
+            min: nanos("min")?,
+            median: nanos_or("median", duration)?,
+            p95: nanos_or("p95", duration)?,
+            max: nanos("max")?,
+            stddev: nanos_or("stddev", Duration::ZERO)?,
+            outliers: number("outliers").map_or(0, |x| x as u128),
+        })
     }
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map = value.measurement.json_fields();
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+
+        JsonValue::Object(map)
     }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        let day = match json.get("day") {
+            Some(JsonValue::Number(day)) if day.fract() == 0.0 && *day >= 0.0 && *day < 256.0 => {
+                Day::new(*day as u8)
+            }
+            _ => None,
+        }
+        .ok_or("Expected part_result.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part_result.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected part_result.answer to be null or string.")?;
+
+        let error = match json.get("error") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected part_result.error to be null or string.")?,
+            ),
+        };
+
+        Ok(PartResult {
+            day,
+            part: part as u8,
+            answer: answer.cloned(),
+            error,
+            measurement: Measurement::try_from(value)?,
+        })
+    }
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        format_error, parse_param, run_params, run_part, run_prepare, BenchOptions, InputSource,
+        Measurement, OutputFormat, PartResult, RunOptions, PARSE_PART,
     };
+    use crate::{day, template::Puzzle};
+    use std::{error::Error, fmt::Display, time::Duration};
+    use tinyjson::JsonValue;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let m = Measurement::from_samples(nanos(&[50, 10, 40, 20, 30]));
+        assert_eq!(m.samples, 5);
+        assert_eq!(m.outliers, 0);
+        assert_eq!(m.duration, Duration::from_nanos(30));
+        assert_eq!(m.min, Duration::from_nanos(10));
+        assert_eq!(m.median, Duration::from_nanos(30));
+        assert_eq!(m.p95, Duration::from_nanos(50));
+        assert_eq!(m.max, Duration::from_nanos(50));
+        assert_eq!(m.stddev.as_nanos(), 14);
+    }
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn rejects_outliers() {
+        let m = Measurement::from_samples(nanos(&[10, 11, 10, 12, 11, 10, 1000]));
+        assert_eq!(m.samples, 7);
+        assert_eq!(m.outliers, 1);
+        assert_eq!(m.max, Duration::from_nanos(12));
+        assert_eq!(m.duration, Duration::from_nanos(10));
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn handles_single_sample() {
+        let m = Measurement::from_samples(nanos(&[42]));
+        assert_eq!(m, Measurement::single(Duration::from_nanos(42)));
+    }
+
+    #[test]
+    fn clamps_iterations() {
+        let options = BenchOptions::DEFAULT;
+        assert_eq!(options.iterations(&Duration::from_secs(2)), 10);
+        assert_eq!(options.iterations(&Duration::from_millis(10)), 100);
+        assert_eq!(options.iterations(&Duration::from_nanos(1)), 10000);
+
+        let options = BenchOptions {
+            min_samples: 20000,
+            ..BenchOptions::DEFAULT
+        };
+        assert_eq!(options.iterations(&Duration::from_nanos(1)), 20000);
+    }
+
+    fn input_source(args: &[&str]) -> Result<InputSource, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_vec(args.iter().map(Into::into).collect());
+        InputSource::from_args(&mut args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(input_source(&["--time"]).unwrap(), InputSource::Input);
+        assert_eq!(
+            input_source(&["--example", "--time"]).unwrap(),
+            InputSource::Example(None)
+        );
+        assert_eq!(
+            input_source(&["--example", "2"]).unwrap(),
+            InputSource::Example(Some(2))
+        );
+        assert_eq!(input_source(&["--input", "-"]).unwrap(), InputSource::Stdin);
+        assert_eq!(
+            input_source(&["--input", "big.txt"]).unwrap(),
+            InputSource::File("big.txt".into())
+        );
+        assert!(input_source(&["--input", "big.txt", "--example"]).is_err());
+        assert_eq!(
+            input_source(&["--example"]).unwrap(),
+            InputSource::Example(None)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_examples() {
+        assert!(input_source(&["--example", "300"]).is_err());
+        assert!(input_source(&["--example", "x"]).is_err());
+    }
+
+    #[test]
+    fn passes_input_sources_on() {
+        for source in [
+            InputSource::Input,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+            InputSource::File("big.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let args = source.to_args();
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            assert_eq!(input_source(&args).unwrap(), source);
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn runs_parts_against_prepared_input() {
+        let options = RunOptions {
+            format: OutputFormat::Json,
+            ..RunOptions::default()
+        };
+        let puzzle = Puzzle::new(2024, day!(1));
+
+        let prepare = |input: &str| -> Result<Vec<u32>, String> {
+            input
+                .split(',')
+                .map(|x| x.parse().map_err(|_| format!("invalid number {x}")))
+                .collect()
+        };
+
+        let (prepared, result) = run_prepare(prepare, "1,x", &(), puzzle, &options);
+        assert_eq!(prepared, None);
+        assert_eq!(result.part, 0);
+        assert_eq!(result.error.as_deref(), Some("invalid number x"));
+
+        let (prepared, _) = run_prepare(prepare, "1,2,3", &(), puzzle, &options);
+        let prepared = prepared.unwrap();
+        let result = run_part(
+            |input: &Vec<u32>| Some(input.iter().sum::<u32>()),
+            &prepared,
+            &(),
+            puzzle,
+            1,
+            &options,
+        );
+        assert_eq!(result.answer, Some("6".into()));
+    }
+
+    #[test]
+    fn reports_part_errors() {
+        let options = RunOptions {
+            format: OutputFormat::Json,
+            ..RunOptions::default()
+        };
+        let puzzle = Puzzle::new(2024, day!(1));
+
+        let part = |input: &str| input.trim().parse::<u32>().map(|x| x * 2);
+        let result = run_part(part, "21", &(), puzzle, 1, &options);
+        assert_eq!((result.answer, result.error), (Some("42".into()), None));
+
+        let result = run_part(part, "x", &(), puzzle, 2, &options);
+        assert_eq!(result.answer, None);
+        assert_eq!(result.error, Some("invalid digit found in string".into()));
+
+        let result = run_part(|_: &str| None::<u32>, "", &(), puzzle, 1, &options);
+        assert_eq!((result.answer, result.error), (None, None));
+    }
+
+    crate::params! {
+        struct Grid {
+            width: usize = 101,
+            wrap: bool = false,
+        }
+    }
+
+    #[test]
+    fn resolves_params() {
+        let puzzle = Puzzle::new(2024, day!(14));
+        let options = |params: &[&str]| RunOptions {
+            format: OutputFormat::Silent,
+            params: params.iter().map(|p| parse_param(p).unwrap()).collect(),
+            ..RunOptions::default()
+        };
+
+        let grid: Grid = run_params(puzzle, &options(&[])).unwrap();
+        assert_eq!(grid, Grid::default());
+
+        let grid: Grid = run_params(puzzle, &options(&["width=11", "wrap = true"])).unwrap();
+        assert_eq!(
+            grid,
+            Grid {
+                width: 11,
+                wrap: true
+            }
+        );
+
+        let part = |input: &str, grid: &Grid| Some(input.len() * grid.width);
+        let result = run_part(part, "ab", &grid, puzzle, 1, &options(&[]));
+        assert_eq!(result.answer, Some("22".into()));
+
+        let results = run_params::<Grid>(puzzle, &options(&["width=-1"])).unwrap_err();
+        assert_eq!(results[0].part, PARSE_PART);
+        assert_eq!(
+            results[0].error.as_deref(),
+            Some("invalid value `-1` for `width`: invalid digit found in string")
+        );
+        let results = run_params::<Grid>(puzzle, &options(&["height=7"])).unwrap_err();
+        assert_eq!(
+            results[0].error.as_deref(),
+            Some("unknown parameter `height`.")
+        );
+        assert!(run_params::<()>(puzzle, &options(&["height=7"])).is_err());
+
+        assert!(parse_param("width").is_err());
+        assert!(parse_param("=1").is_err());
+    }
+
+    #[test]
+    fn formats_error_sources() {
+        #[derive(Debug)]
+        struct Wrapper(std::num::ParseIntError);
+
+        impl Display for Wrapper {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "invalid input")
+            }
+        }
+
+        impl Error for Wrapper {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let error = Wrapper("x".parse::<u8>().unwrap_err());
+        assert_eq!(
+            format_error(&error),
+            "invalid input: invalid digit found in string"
+        );
+
+        let options = RunOptions {
+            format: OutputFormat::Json,
+            ..RunOptions::default()
+        };
+        let prepare = |input: &str| input.parse::<u8>().map_err(Wrapper);
+        let (prepared, result) =
+            run_prepare(prepare, "x", &(), Puzzle::new(2024, day!(18)), &options);
+        assert_eq!(prepared, None);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("invalid input: invalid digit found in string")
+        );
+    }
+
+    #[test]
+    fn serializes_part_results() {
+        let result = PartResult {
+            day: day!(7),
+            part: 1,
+            answer: Some("42".into()),
+            error: None,
+            measurement: Measurement::single(Duration::from_nanos(100)),
+        };
+
+        let json = JsonValue::from(&result);
+        assert!(json.stringify().unwrap().contains(r#""day":7"#));
+        assert_eq!(PartResult::try_from(&json), Ok(result));
+
+        let json: JsonValue =
+            r#"{"day":1.5,"part":1,"answer":null,"duration_ns":0,"samples":1,"min":0,"max":0}"#
+                .parse()
+                .unwrap();
+        assert!(PartResult::try_from(&json).is_err());
+    }
 }
@@ -0,0 +1,523 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{paths, Day};
+
+const SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// Response of the server to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    /// An answer was submitted too recently, the server asks to wait before trying again.
+    Cooldown {
+        seconds: u64,
+    },
+    /// The part was already solved, or is not unlocked yet.
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Interpret the text of the page served after submitting an answer.
+    pub fn from_response(text: &str) -> Option<Self> {
+        if text.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Some(Outcome::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let seconds = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(wait, _)| parse_wait(wait))
+                .unwrap_or(0);
+            Some(Outcome::Cooldown { seconds })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::Cooldown { .. } => "cooldown",
+            Outcome::AlreadySolved => "already_solved",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Outcome::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Outcome::Wrong => write!(f, "That's not the right answer."),
+            Outcome::Cooldown { seconds } => write!(
+                f,
+                "An answer was submitted too recently, try again in {seconds}s."
+            ),
+            Outcome::AlreadySolved => write!(f, "This part is already solved or still locked."),
+        }
+    }
+}
+
+/// Parse wait times of the form `1m 30s` to seconds.
+fn parse_wait(wait: &str) -> Option<u64> {
+    wait.split_whitespace().try_fold(0, |total, token| {
+        let (value, unit) = token.split_at(token.len().checked_sub(1)?);
+        let value: u64 = value.parse().ok()?;
+        let factor = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            _ => return None,
+        };
+        Some(total + value * factor)
+    })
+}
+
+/// Reason why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    Empty,
+    /// The answer is `0`, which usually means the solution found nothing.
+    Zero,
+    Multiline,
+    /// The same answer was submitted before, holding the response of the server.
+    Duplicate(Outcome),
+    /// The part was already solved, holding the accepted answer.
+    Solved(String),
+    /// An answer greater than or equal to this one was reported as too low.
+    TooLow(i128),
+    /// An answer less than or equal to this one was reported as too high.
+    TooHigh(i128),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Empty => write!(f, "the answer is empty."),
+            Rejection::Zero => write!(f, "the answer is 0."),
+            Rejection::Multiline => write!(f, "the answer spans multiple lines."),
+            Rejection::Duplicate(outcome) => {
+                write!(f, "the answer was submitted before. {outcome}")
+            }
+            Rejection::Solved(answer) => {
+                write!(f, "the part was already solved with \"{answer}\".")
+            }
+            Rejection::TooLow(bound) => write!(f, "{bound} was already too low."),
+            Rejection::TooHigh(bound) => write!(f, "{bound} was already too high."),
+        }
+    }
+}
+
+/// A single answer that was submitted to the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Time of the submission, in seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// History of submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate the history to `data/{year}/submissions.json`.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
+        let dir = paths::year_dir(year);
+        fs::create_dir_all(&dir)?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(dir.join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from `data/{year}/submissions.json`. If not present, returns an empty history.
+    /// Fails if the file can not be read or parsed, so the guard never runs against a lost history.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        Submissions::read(&paths::year_dir(year).join(SUBMISSIONS_FILE_NAME))
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => Submissions::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("{}: {e}", paths::display(path)))
+    }
+
+    /// Append a submission to the history.
+    pub fn push(&mut self, day: Day, part: u8, answer: &str, outcome: Outcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp,
+        });
+    }
+
+    /// Reject answers that are empty, `0` or span multiple lines, regardless of the history.
+    pub fn check_answer(answer: &str) -> Result<(), Rejection> {
+        if answer.trim().is_empty() {
+            return Err(Rejection::Empty);
+        }
+
+        if answer.contains('\n') {
+            return Err(Rejection::Multiline);
+        }
+
+        if parse_number(answer) == Some(0) {
+            return Err(Rejection::Zero);
+        }
+
+        Ok(())
+    }
+
+    /// Check `answer` against the history before submitting it.
+    /// Rejects answers that are empty, `0` or span multiple lines, that were submitted before, that
+    /// fall outside the bounds hinted by earlier "too high" / "too low" responses, and answers to
+    /// parts that were already solved. Numeric answers are compared by value, e.g. `042` and `42`.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        Submissions::check_answer(answer)?;
+
+        let previous: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(s) = previous.iter().find(|s| {
+            same_answer(&s.answer, answer) && !matches!(s.outcome, Outcome::Cooldown { .. })
+        }) {
+            return Err(Rejection::Duplicate(s.outcome));
+        }
+
+        if let Some(s) = previous.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Rejection::Solved(s.answer.clone()));
+        }
+
+        if let Some(value) = parse_number(answer) {
+            let bound = |outcome: Outcome| {
+                previous
+                    .iter()
+                    .filter(move |s| s.outcome == outcome)
+                    .filter_map(|s| parse_number(&s.answer))
+            };
+
+            if let Some(low) = bound(Outcome::TooLow).max().filter(|low| value <= *low) {
+                return Err(Rejection::TooLow(low));
+            }
+
+            if let Some(high) = bound(Outcome::TooHigh).min().filter(|high| value >= *high) {
+                return Err(Rejection::TooHigh(high));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether two answers are the same, comparing numbers by value and other answers as text.
+fn same_answer(a: &str, b: &str) -> bool {
+    match (parse_number(a), parse_number(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.trim() == b.trim(),
+    }
+}
+
+/// The value of a numeric answer, ignoring surrounding whitespace.
+fn parse_number(answer: &str) -> Option<i128> {
+    answer.trim().parse().ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.key().into()),
+        );
+        if let Outcome::Cooldown { seconds } = value.outcome {
+            map.insert("wait_seconds".into(), JsonValue::Number(seconds as f64));
+        }
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = match json.get("outcome").and_then(|v| v.get::<String>()) {
+            Some(key) => match key.as_str() {
+                "correct" => Outcome::Correct,
+                "too_high" => Outcome::TooHigh,
+                "too_low" => Outcome::TooLow,
+                "wrong" => Outcome::Wrong,
+                "cooldown" => Outcome::Cooldown {
+                    seconds: number("wait_seconds")? as u64,
+                },
+                "already_solved" => Outcome::AlreadySolved,
+                _ => return Err(format!("Unknown submission outcome `{key}`.")),
+            },
+            None => return Err("Expected submission.outcome to be a string.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part: number("part")? as u8,
+            answer,
+            outcome,
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Rejection, Submissions};
+    use crate::day;
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            Outcome::from_response("That's the right answer! You are one gold star closer."),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            Outcome::from_response("That's not the right answer; your answer is too high."),
+            Some(Outcome::TooHigh)
+        );
+        assert_eq!(
+            Outcome::from_response("That's not the right answer; your answer is too low."),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            Outcome::from_response("That's not the right answer. If you're stuck, ..."),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            Outcome::from_response("You don't seem to be solving the right level."),
+            Some(Outcome::AlreadySolved)
+        );
+        assert_eq!(Outcome::from_response("Something else."), None);
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        assert_eq!(
+            Outcome::from_response("You gave an answer too recently. You have 39s left to wait."),
+            Some(Outcome::Cooldown { seconds: 39 })
+        );
+        assert_eq!(
+            Outcome::from_response("You gave an answer too recently. You have 4m 2s left to wait."),
+            Some(Outcome::Cooldown { seconds: 242 })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        let submissions = Submissions::default();
+        assert_eq!(submissions.check(day!(1), 1, ""), Err(Rejection::Empty));
+        assert_eq!(submissions.check(day!(1), 1, " "), Err(Rejection::Empty));
+        assert_eq!(submissions.check(day!(1), 1, "0"), Err(Rejection::Zero));
+        assert_eq!(submissions.check(day!(1), 1, "-0"), Err(Rejection::Zero));
+        assert_eq!(
+            submissions.check(day!(1), 1, "#.\n.#"),
+            Err(Rejection::Multiline)
+        );
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "41", Outcome::Wrong);
+        submissions.push(day!(1), 1, "42", Outcome::Cooldown { seconds: 30 });
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "41"),
+            Err(Rejection::Duplicate(Outcome::Wrong))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "41"), Ok(()));
+        assert_eq!(
+            submissions.check(day!(1), 1, "041"),
+            Err(Rejection::Duplicate(Outcome::Wrong))
+        );
+    }
+
+    #[test]
+    fn rejects_solved_parts() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "42", Outcome::Correct);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "43"),
+            Err(Rejection::Solved("42".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_answers_out_of_bounds() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "10", Outcome::TooLow);
+        submissions.push(day!(1), 1, "20", Outcome::TooLow);
+        submissions.push(day!(1), 1, "50 ", Outcome::TooHigh);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "15"),
+            Err(Rejection::TooLow(20))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "15 "),
+            Err(Rejection::TooLow(20))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "60"),
+            Err(Rejection::TooHigh(50))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "21"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn reads_history_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-submissions-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("submissions.json");
+
+        assert!(Submissions::read(&path).unwrap().data.is_empty());
+
+        std::fs::write(&path, r#"{ "data": [{ "day": "01" }] }"#).unwrap();
+        assert!(Submissions::read(&path).is_err());
+
+        std::fs::write(&path, "{ \"data\": [").unwrap();
+        assert!(Submissions::read(&path).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn serializes_submissions() {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), 1, "41", Outcome::TooLow);
+        submissions.push(day!(1), 1, "42", Outcome::Cooldown { seconds: 30 });
+
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
@@ -1,9 +1,17 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::runner::{Measurement, PARSE_PART};
+use crate::template::{paths, Calendar, Day};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+const TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +20,70 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Duration of the shared parse stage, for solutions that declare one.
+    pub parse: Option<String>,
+    /// Full benchmark statistics of the parse stage, if recorded.
+    pub parse_stats: Option<Measurement>,
+    /// Full benchmark statistics of part 1, if recorded.
+    pub part_1_stats: Option<Measurement>,
+    /// Full benchmark statistics of part 2, if recorded.
+    pub part_2_stats: Option<Measurement>,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            day: crate::day!(1),
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            parse: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+        }
+    }
+}
+
+impl Timing {
+    /// Fill the parts missing from `self` with the parts of `stored`. Kept parts add their mean
+    /// duration to the total if `stored` has statistics for them.
+    fn merge(&self, stored: &Self) -> Self {
+        let mut timing = self.clone();
+
+        for (part, stats, stored_part, stored_stats) in [
+            (
+                &mut timing.parse,
+                &mut timing.parse_stats,
+                &stored.parse,
+                stored.parse_stats,
+            ),
+            (
+                &mut timing.part_1,
+                &mut timing.part_1_stats,
+                &stored.part_1,
+                stored.part_1_stats,
+            ),
+            (
+                &mut timing.part_2,
+                &mut timing.part_2_stats,
+                &stored.part_2,
+                stored.part_2_stats,
+            ),
+        ] {
+            if part.is_some() || stored_part.is_none() {
+                continue;
+            }
+            part.clone_from(stored_part);
+            *stats = stored_stats;
+            #[allow(clippy::cast_precision_loss)]
+            if let Some(stored_stats) = stored_stats {
+                timing.total_nanos += stored_stats.duration.as_nanos() as f64;
+            }
+        }
+
+        timing
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,27 +94,40 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to `data/{year}/timings.json`.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
+        let dir = paths::year_dir(year);
+        fs::create_dir_all(&dir)?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(dir.join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from `data/{year}/timings.json`. If not present, returns empty timings.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        Timings::read(&paths::year_dir(year).join(TIMINGS_FILE_NAME))
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => Timings::try_from(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("{}: {e}", paths::display(path)))
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Parts that are missing from a day in `other`, e.g. because the day failed or timed out, keep
+    /// their timing from `self`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            match self.data.iter().find(|t| t.day == timing.day) {
+                Some(stored) => data.push(timing.merge(stored)),
+                None => data.push(timing.clone()),
+            }
         }
 
         for timing in &self.data {
@@ -51,7 +136,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,10 +145,75 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Whether every part `calendar` has for `day` is benched.
+    pub fn is_day_complete(&self, day: Day, calendar: &Calendar) -> bool {
+        self.data.iter().any(|t| {
+            t.day == day && t.part_1.is_some() && (t.part_2.is_some() || !calendar.has_part(day, 2))
+        })
+    }
+
+    /// Compare the median of every part of `days` in `self` against the same part in `baseline`.
+    /// Parts without statistics in `baseline` are reported without a baseline. Parts without
+    /// statistics in `self`, e.g. because their day failed or timed out, are reported as missing.
+    pub fn compare(&self, baseline: &Self, days: &HashSet<Day>) -> Vec<Comparison> {
+        let mut days: Vec<Day> = days.iter().copied().collect();
+        days.sort_unstable();
+
+        days.into_iter()
+            .flat_map(|day| {
+                let stats = |timings: &Timings| {
+                    timings
+                        .data
+                        .iter()
+                        .find(|t| t.day == day)
+                        .map(|t| [t.parse_stats, t.part_1_stats, t.part_2_stats])
+                        .unwrap_or_default()
+                };
+                let (new, old) = (stats(self), stats(baseline));
+
+                [PARSE_PART, 1, 2]
+                    .into_iter()
+                    .zip(new.into_iter().zip(old))
+                    .filter(|(_, (new, old))| new.is_some() || old.is_some())
+                    .map(move |(part, (new, old))| Comparison {
+                        day,
+                        part,
+                        old: old.map(|m| m.median),
+                        new: new.map(|m| m.median),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Change in median duration of a single part between two sets of timings.
+/// Part [`PARSE_PART`] refers to the parse stage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub old: Option<Duration>,
+    /// Median of the new run, `None` if the part did not run or failed.
+    pub new: Option<Duration>,
+}
+
+impl Comparison {
+    /// Relative change from `old` to `new` in percent, positive if the part got slower.
+    pub fn change_percent(&self) -> Option<f64> {
+        let old = self.old?.as_secs_f64();
+        if old == 0.0 {
+            return None;
+        }
+        Some((self.new?.as_secs_f64() - old) / old * 100.0)
+    }
+
+    /// Whether the part got slower by more than `threshold` percent, or is missing from the new run
+    /// despite having a baseline.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        match (self.old, self.new) {
+            (Some(_), None) => true,
+            _ => self.change_percent().is_some_and(|x| x > threshold),
+        }
     }
 }
 
@@ -133,6 +283,28 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match &value.parse {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +338,33 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse timings and statistics are optional so timings stored before they were
+        // introduced still load.
+        let parse = match json.get("parse") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let stats = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => Measurement::try_from(v)
+                .map(Some)
+                .map_err(|e| format!("Expected timing.{key} to be null or a measurement: {e}")),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse,
+            parse_stats: stats("parse_stats")?,
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
         })
     }
 }
@@ -191,18 +385,20 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -221,6 +417,29 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.part_1_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "duration_ns": 1000000, "samples": 10, "min": 900000, "median": 1000000, "p95": 1100000, "max": 1200000, "stddev": 50000, "outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.p95.as_nanos(), 1_100_000);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 4000000, "parse": "1ms" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("1ms".to_string()));
+            assert_eq!(timing.parse_stats, None);
         }
 
         #[test]
@@ -243,6 +462,23 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        fn reads_timing_files() {
+            let dir = std::env::temp_dir().join(format!("aoc-timings-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("timings.json");
+
+            assert!(Timings::read(&path).unwrap().data.is_empty());
+
+            std::fs::write(&path, r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#).unwrap();
+            assert_eq!(Timings::read(&path).unwrap().data[0].day, day!(1));
+
+            std::fs::write(&path, r#"{ "data": [{ "day": "01" }] }"#).unwrap();
+            assert!(Timings::read(&path).is_err());
+
+            std::fs::remove_dir_all(dir).unwrap();
+        }
     }
 
     mod serialization {
@@ -272,6 +508,7 @@ mod tests {
         use crate::{
             day,
             template::timings::{Timing, Timings},
+            template::Calendar,
         };
 
         #[test]
@@ -282,10 +519,14 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(
+                timings.is_day_complete(day!(1), &Calendar::default_for_year(2024)),
+                true
+            );
         }
 
         #[test]
@@ -294,12 +535,32 @@ mod tests {
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
+                }],
+            };
+
+            assert_eq!(
+                timings.is_day_complete(day!(1), &Calendar::default_for_year(2024)),
+                false
+            );
+        }
+
+        #[test]
+        fn handles_single_part_final_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(25),
+                    part_1: Some("1ms".into()),
+                    total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(day!(25), &Calendar::default_for_year(2024)),
+                true
+            );
         }
 
         #[test]
@@ -307,20 +568,100 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(day!(1), &Calendar::default_for_year(2024)),
+                false
+            );
         }
     }
 
-    mod merge {
+    mod compare {
+        use std::{collections::HashSet, time::Duration};
+
         use crate::{
             day,
+            template::runner::Measurement,
             template::timings::{Timing, Timings},
+            template::Day,
+        };
+
+        fn days() -> HashSet<Day> {
+            HashSet::from([day!(1)])
+        }
+
+        fn timings(part_1: Option<u64>, part_2: Option<u64>) -> Timings {
+            let stats =
+                |nanos: Option<u64>| nanos.map(|x| Measurement::single(Duration::from_nanos(x)));
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1_stats: stats(part_1),
+                    part_2_stats: stats(part_2),
+                    ..Default::default()
+                }],
+            }
+        }
+
+        #[test]
+        fn computes_changes() {
+            let baseline = timings(Some(100), Some(200));
+            let comparisons = timings(Some(150), Some(100)).compare(&baseline, &days());
+            assert_eq!(comparisons.len(), 2);
+            assert_eq!(comparisons[0].change_percent().unwrap().round(), 50.0);
+            assert_eq!(comparisons[1].change_percent().unwrap().round(), -50.0);
+            assert_eq!(comparisons[0].is_regression(10.0), true);
+            assert_eq!(comparisons[0].is_regression(60.0), false);
+            assert_eq!(comparisons[1].is_regression(10.0), false);
+        }
+
+        #[test]
+        fn handles_missing_baseline() {
+            let comparisons = timings(Some(150), None).compare(&timings(None, None), &days());
+            assert_eq!(comparisons.len(), 1);
+            assert_eq!(comparisons[0].old, None);
+            assert_eq!(comparisons[0].change_percent(), None);
+            assert_eq!(comparisons[0].is_regression(0.0), false);
+        }
+
+        #[test]
+        fn handles_missing_days() {
+            let comparisons = timings(Some(150), Some(100)).compare(&Timings::default(), &days());
+            assert_eq!(comparisons.len(), 2);
+            assert!(comparisons.iter().all(|c| c.old.is_none()));
+
+            let comparisons =
+                timings(Some(150), Some(100)).compare(&Timings::default(), &HashSet::new());
+            assert!(comparisons.is_empty());
+        }
+
+        #[test]
+        fn reports_missing_parts_as_regressions() {
+            let baseline = timings(Some(100), Some(200));
+            let comparisons = timings(Some(100), None).compare(&baseline, &days());
+            assert_eq!(comparisons.len(), 2);
+            assert_eq!(comparisons[1].new, None);
+            assert_eq!(comparisons[1].is_regression(10.0), true);
+
+            // a day that failed or timed out has no timings at all.
+            let comparisons = Timings::default().compare(&baseline, &days());
+            assert_eq!(comparisons.len(), 2);
+            assert!(comparisons.iter().all(|c| c.is_regression(10.0)));
+        }
+    }
+
+    mod merge {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                runner::Measurement,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -331,9 +672,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +690,10 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    part_1: Some("1ms".into()),
+                    part_2: Some("2ms".into()),
+                    total_nanos: 3e+6,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,10 +701,50 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1, Some("1ms".into()));
+            assert_eq!(merged.data[1].total_nanos, 3e+6);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_parts_missing_from_partial_timings() {
+            let stats = |nanos| Measurement {
+                duration: Duration::from_nanos(nanos),
+                ..Default::default()
+            };
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    parse: Some("5ns".into()),
+                    parse_stats: Some(stats(5)),
+                    part_1: Some("10ns".into()),
+                    part_1_stats: Some(stats(10)),
+                    part_2: Some("20ns".into()),
+                    part_2_stats: Some(stats(20)),
+                    total_nanos: 35_f64,
+                }],
+            };
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    parse: Some("4ns".into()),
+                    parse_stats: Some(stats(4)),
+                    part_1: Some("8ns".into()),
+                    part_1_stats: Some(stats(8)),
+                    total_nanos: 12_f64,
+                    ..Default::default()
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 1);
+            assert_eq!(merged.data[0].parse, Some("4ns".into()));
+            assert_eq!(merged.data[0].part_1, Some("8ns".into()));
+            assert_eq!(merged.data[0].part_2, Some("20ns".into()));
+            assert_eq!(merged.data[0].part_2_stats, Some(stats(20)));
+            assert_eq!(merged.data[0].total_nanos, 32_f64);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();